@@ -1,6 +1,7 @@
 //! Service and ServiceFactory implementation. Specialized wrapper over substrate service.
 
 use futures::FutureExt;
+use pallet_transaction_storage::runtime_api::TransactionStorageApi;
 use polkadot_bulletin_chain_runtime as runtime;
 use runtime::{opaque::Block, RuntimeApi};
 use sc_client_api::{Backend, BlockBackend};
@@ -9,6 +10,12 @@ pub use sc_executor::NativeElseWasmExecutor;
 use sc_service::{error::Error as ServiceError, Configuration, TaskManager, WarpSyncParams};
 use sc_telemetry::{Telemetry, TelemetryWorker};
 use sc_transaction_pool_api::OffchainTransactionPoolFactory;
+use sp_api::ProvideRuntimeApi;
+use sp_runtime::traits::Block as BlockT;
+use sp_transaction_storage_proof::{
+	registration::build_proof, IndexedBody,
+	InherentDataProvider as StorageProofInherentDataProvider, DEFAULT_STORAGE_PERIOD,
+};
 use std::{sync::Arc, time::Duration};
 
 // Our native executor instance.
@@ -38,6 +45,33 @@ type FullSelectChain = sc_consensus::LongestChain<FullBackend, Block>;
 type FullGrandpaBlockImport =
 	sc_consensus_grandpa::GrandpaBlockImport<FullBackend, Block, FullClient, FullSelectChain>;
 
+/// Creates the storage proof inherent data provider for a block built on `parent`. As
+/// `sp_transaction_storage_proof::registration::new_data_provider`, but the proved chunk is
+/// selected with the runtime's storage proof randomness rather than the parent hash, so that
+/// block authors cannot choose which chunk they have to prove.
+fn storage_proof_data_provider(
+	client: &FullClient,
+	parent: <Block as BlockT>::Hash,
+) -> Result<StorageProofInherentDataProvider, Box<dyn std::error::Error + Send + Sync>> {
+	let parent_number = IndexedBody::<Block>::number(client, parent)?.unwrap_or_default();
+	let number = parent_number.saturating_add(1).saturating_sub(DEFAULT_STORAGE_PERIOD);
+	if number == 0 {
+		// Too early to collect proofs
+		return Ok(StorageProofInherentDataProvider::new(None))
+	}
+
+	let proof = match IndexedBody::<Block>::block_indexed_body(client, number)? {
+		Some(transactions) if !transactions.is_empty() => {
+			let randomness = client.runtime_api().storage_proof_randomness(parent)?;
+			let random_hash = randomness.unwrap_or_else(|| parent.into());
+			Some(build_proof(&random_hash, transactions)?)
+		},
+		// Nothing was indexed in that block
+		Some(_) | None => None,
+	};
+	Ok(StorageProofInherentDataProvider::new(proof))
+}
+
 #[allow(clippy::type_complexity)]
 pub fn new_partial(
 	config: &Configuration,
@@ -271,11 +305,7 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
 							slot_duration,
 						);
 
-					let storage_proof =
-						sp_transaction_storage_proof::registration::new_data_provider(
-							&*client_clone,
-							&parent,
-						)?;
+					let storage_proof = storage_proof_data_provider(&client_clone, parent)?;
 
 					Ok((slot, timestamp, storage_proof))
 				}
@@ -131,7 +131,8 @@ mod benchmarks {
 		_(RawOrigin::None, vec![0u8; l as usize]);
 
 		assert!(!BlockTransactions::<T>::get().is_empty());
-		assert_last_event::<T>(Event::Stored { index: 0 }.into());
+		let cid = cid(&sp_io::hashing::blake2_256(&vec![0u8; l as usize]));
+		assert_last_event::<T>(Event::Stored { index: 0, cid, namespace: None }.into());
 		Ok(())
 	}
 
@@ -237,5 +238,450 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn begin_upload() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let root = [0u8; 32];
+		let chunk_count = T::MaxUploadChunks::get();
+		let size = chunk_count as u64;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), root, size, chunk_count);
+
+		assert_last_event::<T>(
+			Event::UploadStarted { session: 0, who: caller, root, size, chunk_count }.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn upload_chunk(
+		l: Linear<{ 1 }, { T::MaxTransactionSize::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		TransactionStorage::<T>::begin_upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			[0u8; 32],
+			l as u64,
+			1,
+		)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), 0, 0, vec![0u8; l as usize]);
+
+		assert_last_event::<T>(Event::UploadChunkStored { session: 0, chunk: 0, index: 0 }.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn finalize_upload(c: Linear<1, { T::MaxUploadChunks::get() }>) -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let chunk_hashes: Vec<ContentHash> =
+			(0..c).map(|i| sp_io::hashing::blake2_256(&i.encode())).collect();
+		let root: ContentHash = sp_io::trie::blake2_256_ordered_root(
+			chunk_hashes.iter().map(|hash| hash.to_vec()).collect(),
+			sp_runtime::StateVersion::V1,
+		)
+		.into();
+		UploadSessions::<T>::insert(
+			0,
+			UploadSession {
+				owner: caller.clone(),
+				root,
+				size: c as u64,
+				chunk_count: c,
+				chunk_hashes: chunk_hashes.try_into().expect("c <= MaxUploadChunks; qed"),
+				received: c as u64,
+				expires_at: T::UploadTimeout::get(),
+				first_block: Zero::zero(),
+				last_block: Zero::zero(),
+			},
+		);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), 0);
+
+		let receipt = UploadReceipt {
+			root,
+			size: c as u64,
+			chunk_count: c,
+			first_block: Zero::zero(),
+			last_block: Zero::zero(),
+		};
+		assert_last_event::<T>(Event::UploadFinalized { session: 0, receipt }.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn store_in_namespace(
+		l: Linear<{ 1 }, { T::MaxTransactionSize::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let namespace: Namespace = b"benchmark".to_vec().try_into().unwrap();
+		NamespaceConfigs::<T>::insert(
+			&namespace,
+			NamespaceConfig {
+				max_item_size: T::MaxTransactionSize::get(),
+				retention: T::StoragePeriod::get(),
+				max_total_bytes: u64::MAX,
+			},
+		);
+
+		#[extrinsic_call]
+		_(RawOrigin::None, namespace.clone(), vec![0u8; l as usize]);
+
+		let cid = cid(&sp_io::hashing::blake2_256(&vec![0u8; l as usize]));
+		assert_last_event::<T>(Event::Stored { index: 0, cid, namespace: Some(namespace) }.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_namespace_config() -> Result<(), BenchmarkError> {
+		let origin = T::Authorizer::try_successful_origin()
+			.map_err(|_| BenchmarkError::Stop("unable to compute origin"))?;
+		let namespace: Namespace = b"benchmark".to_vec().try_into().unwrap();
+		let config = Some(NamespaceConfig {
+			max_item_size: T::MaxTransactionSize::get(),
+			retention: T::StoragePeriod::get(),
+			max_total_bytes: u64::MAX,
+		});
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, namespace.clone(), config.clone());
+
+		assert_last_event::<T>(Event::NamespaceConfigSet { namespace, config }.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn retract() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let data = vec![0u8; T::MaxTransactionSize::get() as usize];
+		let content_hash = sp_io::hashing::blake2_256(&data);
+		TransactionStorage::<T>::store(RawOrigin::Signed(caller.clone()).into(), data)?;
+		// Retract a copy with both an older and a more recent copy, which must both be relinked
+		for block in 0..2u32 {
+			run_to_block::<T>((block + 1).into());
+			TransactionStorage::<T>::renew(
+				RawOrigin::Signed(caller.clone()).into(),
+				block.into(),
+				0,
+			)?;
+		}
+		run_to_block::<T>(3u32.into());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), One::one(), 0);
+
+		assert_last_event::<T>(
+			Event::Retracted { block: One::one(), index: 0, content_hash }.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn resume_upload() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		TransactionStorage::<T>::begin_upload(
+			RawOrigin::Signed(caller.clone()).into(),
+			[0u8; 32],
+			1,
+			1,
+		)?;
+		let expires_at = System::<T>::block_number() + T::UploadTimeout::get();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), 0);
+
+		assert_last_event::<T>(Event::UploadResumed { session: 0, expires_at }.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn store_operational(
+		l: Linear<{ 1 }, { T::MaxTransactionSize::get() }>,
+	) -> Result<(), BenchmarkError> {
+		#[extrinsic_call]
+		_(RawOrigin::None, vec![0u8; l as usize]);
+
+		assert!(!BlockTransactions::<T>::get().is_empty());
+		let cid = cid(&sp_io::hashing::blake2_256(&vec![0u8; l as usize]));
+		assert_last_event::<T>(Event::Stored { index: 0, cid, namespace: None }.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn transfer_quota() -> Result<(), BenchmarkError> {
+		let origin = T::Authorizer::try_successful_origin()
+			.map_err(|_| BenchmarkError::Stop("unable to compute origin"))?;
+		let caller: T::AccountId = whitelisted_caller();
+		let dest: T::AccountId = account("dest", 0, 0);
+		TransactionStorage::<T>::authorize_account(origin, caller.clone(), 2, 2)
+			.map_err(|_| BenchmarkError::Stop("unable to authorize account"))?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), dest.clone(), 1, 1);
+
+		assert_last_event::<T>(
+			Event::AuthorizationTransferred { from: caller, to: dest, transactions: 1, bytes: 1 }
+				.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn delegate() -> Result<(), BenchmarkError> {
+		let origin = T::Authorizer::try_successful_origin()
+			.map_err(|_| BenchmarkError::Stop("unable to compute origin"))?;
+		let caller: T::AccountId = whitelisted_caller();
+		let delegate: T::AccountId = account("delegate", 0, 0);
+		TransactionStorage::<T>::authorize_account(origin, caller.clone(), 1, 1)
+			.map_err(|_| BenchmarkError::Stop("unable to authorize account"))?;
+		let expiration = System::<T>::block_number() + T::AuthorizationPeriod::get();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), delegate.clone(), 1, expiration);
+
+		assert_last_event::<T>(
+			Event::Delegated { owner: caller, delegate, bytes_limit: 1, expiration }.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn revoke_delegation() -> Result<(), BenchmarkError> {
+		let origin = T::Authorizer::try_successful_origin()
+			.map_err(|_| BenchmarkError::Stop("unable to compute origin"))?;
+		let caller: T::AccountId = whitelisted_caller();
+		let delegate: T::AccountId = account("delegate", 0, 0);
+		TransactionStorage::<T>::authorize_account(origin, caller.clone(), 1, 1)
+			.map_err(|_| BenchmarkError::Stop("unable to authorize account"))?;
+		let expiration = System::<T>::block_number() + T::AuthorizationPeriod::get();
+		TransactionStorage::<T>::delegate(
+			RawOrigin::Signed(caller.clone()).into(),
+			delegate.clone(),
+			1,
+			expiration,
+		)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), delegate.clone());
+
+		assert_last_event::<T>(Event::DelegationRevoked { owner: caller, delegate }.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn store_with_attestation(
+		l: Linear<{ 1 }, { T::MaxTransactionSize::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let data = vec![0u8; l as usize];
+		let content_hash = sp_io::hashing::blake2_256(&data);
+		let key_type = sp_runtime::KeyTypeId(*b"bnch");
+		let public = sp_io::crypto::sr25519_generate(key_type, None);
+		let content_type: BoundedVec<_, _> = b"text/plain".to_vec().try_into().unwrap();
+		let message = (&content_hash, &content_type, 1u32).encode();
+		let signature = sp_io::crypto::sr25519_sign(key_type, &public, &message)
+			.ok_or(BenchmarkError::Stop("unable to sign attestation"))?;
+		let attestation = Attestation {
+			content_type,
+			schema_version: 1,
+			signer: public.into(),
+			signature: signature.into(),
+		};
+
+		#[extrinsic_call]
+		_(RawOrigin::None, attestation.clone(), data);
+
+		assert_last_event::<T>(Event::Attested { index: 0, attestation }.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn store_encoded(
+		l: Linear<{ 17 }, { T::MaxTransactionSize::get() }>,
+	) -> Result<(), BenchmarkError> {
+		// A Zstandard frame of as many single byte RLE blocks as fit, which takes longest to
+		// check
+		let blocks = (l - 13) / 4;
+		let mut data = vec![0x28, 0xb5, 0x2f, 0xfd, 0xe0];
+		data.extend_from_slice(&u64::from(blocks).to_le_bytes());
+		for block in 0..blocks {
+			let last = if block == blocks - 1 { 1 } else { 0 };
+			data.extend_from_slice(&[(1 << 3) | (1 << 1) | last, 0, 0, 0]);
+		}
+
+		#[extrinsic_call]
+		_(RawOrigin::None, ContentEncoding::Zstd, data);
+
+		assert_last_event::<T>(
+			Event::Encoded {
+				index: 0,
+				encoding: ContentEncoding::Zstd,
+				decoded_size: blocks.into(),
+			}
+			.into(),
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn claim_name() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let data = vec![0u8; 1];
+		let content_hash = sp_io::hashing::blake2_256(&data);
+		TransactionStorage::<T>::store(RawOrigin::None.into(), data)?;
+		let name: Name = vec![0u8; MAX_NAME_LEN as usize].try_into().unwrap();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), name.clone(), content_hash);
+
+		assert_last_event::<T>(Event::NameClaimed { name, owner: caller, content_hash }.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn point_name() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let previous = sp_io::hashing::blake2_256(&[0]);
+		TransactionStorage::<T>::store(RawOrigin::None.into(), vec![0])?;
+		let content_hash = sp_io::hashing::blake2_256(&[1]);
+		TransactionStorage::<T>::store(RawOrigin::None.into(), vec![1])?;
+		let name: Name = vec![0u8; MAX_NAME_LEN as usize].try_into().unwrap();
+		TransactionStorage::<T>::claim_name(
+			RawOrigin::Signed(caller.clone()).into(),
+			name.clone(),
+			previous,
+		)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), name.clone(), content_hash);
+
+		assert_last_event::<T>(Event::NamePointed { name, content_hash, previous }.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn release_name() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let content_hash = sp_io::hashing::blake2_256(&[0]);
+		TransactionStorage::<T>::store(RawOrigin::None.into(), vec![0])?;
+		let name: Name = vec![0u8; MAX_NAME_LEN as usize].try_into().unwrap();
+		TransactionStorage::<T>::claim_name(
+			RawOrigin::Signed(caller.clone()).into(),
+			name.clone(),
+			content_hash,
+		)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), name.clone());
+
+		assert_last_event::<T>(Event::NameReleased { name }.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn pin() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let data = vec![0u8; T::MaxTransactionSize::get() as usize];
+		let content_hash = sp_io::hashing::blake2_256(&data);
+		TransactionStorage::<T>::store(RawOrigin::None.into(), data)?;
+		run_to_block::<T>(1u32.into());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), content_hash);
+
+		assert_last_event::<T>(Event::Renewed { index: 0 }.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_usage_threshold() -> Result<(), BenchmarkError> {
+		let origin = T::Authorizer::try_successful_origin()
+			.map_err(|_| BenchmarkError::Stop("unable to compute origin"))?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, Some(u64::MAX));
+
+		assert_last_event::<T>(Event::UsageThresholdSet { threshold: Some(u64::MAX) }.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_authorization_rate() -> Result<(), BenchmarkError> {
+		let origin = T::Authorizer::try_successful_origin()
+			.map_err(|_| BenchmarkError::Stop("unable to compute origin"))?;
+		let who: T::AccountId = whitelisted_caller();
+		TransactionStorage::<T>::authorize_account(origin.clone(), who.clone(), 1, 1)
+			.map_err(|_| BenchmarkError::Stop("unable to authorize account"))?;
+		let limit = Some(RateLimit { bytes: 1, period: One::one() });
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, who.clone(), limit);
+
+		assert_last_event::<T>(Event::AuthorizationRateSet { who, limit }.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn commit() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let content_hash = [0u8; 32];
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), content_hash);
+
+		assert_last_event::<T>(Event::Committed { owner: caller, content_hash }.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn reveal(l: Linear<{ 1 }, { T::MaxTransactionSize::get() }>) -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let data = vec![0u8; l as usize];
+		TransactionStorage::<T>::commit(
+			RawOrigin::Signed(caller.clone()).into(),
+			sp_io::hashing::blake2_256(&data),
+		)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), data);
+
+		assert_last_event::<T>(Event::Revealed { index: 0, committed: Zero::zero() }.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn force_set_authorizations(
+		n: Linear<{ 1 }, { MAX_FORCED_AUTHORIZATIONS }>,
+	) -> Result<(), BenchmarkError> {
+		let expiration = System::<T>::block_number() + T::AuthorizationPeriod::get();
+		let details = AuthorizationDetails { transactions: 1, bytes: 1, expiration };
+		let authorizations: Vec<_> = (0..n)
+			.map(|i| (account::<T::AccountId>("who", i, 0), details.clone()))
+			.collect();
+		let authorizations: BoundedVec<_, _> =
+			authorizations.try_into().expect("n <= MAX_FORCED_AUTHORIZATIONS; qed");
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, authorizations);
+
+		assert_last_event::<T>(Event::AuthorizationsForceSet { count: n }.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_standing_authorization() -> Result<(), BenchmarkError> {
+		let origin = T::Authorizer::try_successful_origin()
+			.map_err(|_| BenchmarkError::Stop("unable to compute origin"))?;
+		let who: T::AccountId = whitelisted_caller();
+		let quota = Some(StandingQuota { transactions: 1, bytes: 1, period: One::one() });
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, who.clone(), quota);
+
+		assert_last_event::<T>(Event::StandingAuthorizationSet { who, quota }.into());
+		Ok(())
+	}
+
 	impl_benchmark_test_suite!(TransactionStorage, crate::mock::new_test_ext(), crate::mock::Test);
 }
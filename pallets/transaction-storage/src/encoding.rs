@@ -0,0 +1,92 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Decoded sizes of encoded data, read from the encoding's framing without decompressing.
+
+use crate::ContentEncoding;
+
+/// Zstandard frame magic number, little endian.
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// Returns the decoded size of `data` in bytes, as declared by its encoding, or `None` if `data`
+/// is not framed as the encoding requires.
+pub(crate) fn decoded_size(encoding: ContentEncoding, data: &[u8]) -> Option<u64> {
+	match encoding {
+		ContentEncoding::Identity => Some(data.len() as u64),
+		ContentEncoding::Zstd => zstd_decoded_size(data),
+	}
+}
+
+/// Reads little endian `bytes` as an integer.
+fn read_le(bytes: &[u8]) -> u64 {
+	bytes.iter().rev().fold(0, |value, byte| (value << 8) | u64::from(*byte))
+}
+
+/// Returns the content size of a single Zstandard frame spanning all of `data`. The frame must
+/// declare its content size; decoders reject frames whose content does not match it. The blocks
+/// are walked to check that nothing follows the frame.
+fn zstd_decoded_size(data: &[u8]) -> Option<u64> {
+	let rest = data.strip_prefix(&ZSTD_MAGIC[..])?;
+	let (&descriptor, mut rest) = rest.split_first()?;
+	// Reserved bit must be zero
+	if descriptor & 0x08 != 0 {
+		return None
+	}
+	let single_segment = descriptor & 0x20 != 0;
+	let has_checksum = descriptor & 0x04 != 0;
+	let dictionary_id_len = [0, 1, 2, 4][usize::from(descriptor & 0x03)];
+	let content_size_len = match descriptor >> 6 {
+		0 if single_segment => 1,
+		0 => return None,
+		1 => 2,
+		2 => 4,
+		_ => 8,
+	};
+
+	let window_descriptor_len = if single_segment { 0 } else { 1 };
+	let header_len = window_descriptor_len + dictionary_id_len + content_size_len;
+	if rest.len() < header_len {
+		return None
+	}
+	let content_size = read_le(&rest[header_len - content_size_len..header_len]);
+	// Two byte sizes are offset by 256
+	let content_size = if content_size_len == 2 { content_size + 256 } else { content_size };
+	rest = &rest[header_len..];
+
+	loop {
+		if rest.len() < 3 {
+			return None
+		}
+		let block_header = read_le(&rest[..3]);
+		let last = block_header & 0x01 != 0;
+		let block_size = (block_header >> 3) as usize;
+		let block_len = match (block_header >> 1) & 0x03 {
+			// Raw and compressed blocks
+			0 | 2 => block_size,
+			// RLE blocks repeat a single byte
+			1 => 1,
+			_ => return None,
+		};
+		rest = rest.get(3 + block_len..)?;
+		if last {
+			break
+		}
+	}
+
+	let checksum_len = if has_checksum { 4 } else { 0 };
+	(rest.len() == checksum_len).then_some(content_size)
+}
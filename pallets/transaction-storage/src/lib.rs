@@ -20,11 +20,18 @@
 //! This pallet is designed to be used on chains with no transaction fees. It must be used with a
 //! `SignedExtension` implementation that calls the [`validate_signed`](Pallet::validate_signed)
 //! and [`pre_dispatch_signed`](Pallet::pre_dispatch_signed) functions.
+//!
+//! As there are no balances, an account authorization provides a reference to the account: the
+//! account is created when first authorized, and reaped once its authorization is removed after
+//! expiring. Used up account authorizations are kept until they expire, as reaping an account
+//! resets its nonce, which would allow its transactions to be replayed.
 
 // Ensure we're `no_std` when compiling for Wasm.
 #![cfg_attr(not(feature = "std"), no_std)]
 
 mod benchmarking;
+mod encoding;
+pub mod runtime_api;
 pub mod weights;
 
 #[cfg(test)]
@@ -33,10 +40,16 @@ mod mock;
 mod tests;
 
 use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	traits::{ConstU32, Get, Randomness},
+	weights::{Weight, WeightMeter},
+	BoundedVec,
+};
 use frame_system::pallet_prelude::BlockNumberFor;
 use sp_runtime::{
-	traits::{BlakeTwo256, Hash, One, Saturating, Zero},
+	traits::{BlakeTwo256, Hash, One, Saturating, Verify, Zero},
 	transaction_validity::InvalidTransaction,
+	AccountId32, MultiSignature,
 };
 use sp_std::{prelude::*, result};
 use sp_transaction_storage_proof::{
@@ -48,6 +61,8 @@ use sp_transaction_storage_proof::{
 pub use pallet::*;
 pub use weights::WeightInfo;
 
+const LOG_TARGET: &str = "runtime::transaction-storage";
+
 /// Maximum bytes that can be stored in one transaction.
 // Setting higher limit also requires raising the allocator limit.
 pub const DEFAULT_MAX_TRANSACTION_SIZE: u32 = 8 * 1024 * 1024;
@@ -63,6 +78,52 @@ pub const RENEWED_NOT_FOUND: InvalidTransaction = InvalidTransaction::Custom(2);
 pub const AUTHORIZATION_NOT_FOUND: InvalidTransaction = InvalidTransaction::Custom(3);
 /// Authorization has not expired.
 pub const AUTHORIZATION_NOT_EXPIRED: InvalidTransaction = InvalidTransaction::Custom(4);
+/// Upload size or chunk count is not in the allowed range.
+pub const BAD_UPLOAD_PARAMETERS: InvalidTransaction = InvalidTransaction::Custom(5);
+/// Upload session was not found, or is owned by a different account.
+pub const UPLOAD_NOT_FOUND: InvalidTransaction = InvalidTransaction::Custom(6);
+/// Upload chunk is out of order or exceeds the declared upload size.
+pub const UNEXPECTED_UPLOAD_CHUNK: InvalidTransaction = InvalidTransaction::Custom(7);
+/// Not all chunks of the upload have been supplied.
+pub const UPLOAD_INCOMPLETE: InvalidTransaction = InvalidTransaction::Custom(8);
+/// Namespace is not configured, or data exceeds its maximum item size or total size.
+pub const BAD_NAMESPACE: InvalidTransaction = InvalidTransaction::Custom(9);
+/// Data has been kept for the full retention period of its namespace and can't be renewed.
+pub const RETENTION_EXCEEDED: InvalidTransaction = InvalidTransaction::Custom(10);
+/// Retracted data not found.
+pub const RETRACTED_NOT_FOUND: InvalidTransaction = InvalidTransaction::Custom(11);
+/// Upload session has timed out and must be resumed before more chunks can be supplied.
+pub const UPLOAD_TIMED_OUT: InvalidTransaction = InvalidTransaction::Custom(12);
+/// Delegation not found.
+pub const DELEGATION_NOT_FOUND: InvalidTransaction = InvalidTransaction::Custom(13);
+/// Attestation signature is not valid for the data.
+pub const BAD_ATTESTATION: InvalidTransaction = InvalidTransaction::Custom(14);
+/// Data is not framed as its declared encoding requires.
+pub const BAD_ENCODING: InvalidTransaction = InvalidTransaction::Custom(15);
+/// Name is already claimed, is not owned by the signer, or would point to data that is not being
+/// kept.
+pub const BAD_NAME: InvalidTransaction = InvalidTransaction::Custom(16);
+/// Account authorization rate limit reached; the transaction may be valid in a later period.
+pub const RATE_LIMITED: InvalidTransaction = InvalidTransaction::Custom(17);
+/// Data has already been committed to by the signer or is being kept, or the signer has no
+/// commitment to reveal it that has not expired.
+pub const BAD_COMMITMENT: InvalidTransaction = InvalidTransaction::Custom(18);
+
+/// Maximum length of an [`Attestation`] content type in bytes.
+pub const MAX_CONTENT_TYPE_LEN: u32 = 64;
+
+/// Maximum length of a [`Name`] in bytes.
+pub const MAX_NAME_LEN: u32 = 64;
+
+/// Maximum length of a [`Namespace`] in bytes.
+pub const MAX_NAMESPACE_LEN: u32 = 32;
+
+/// Maximum number of entries returned by one [`expiring_within`](Pallet::expiring_within) call.
+pub const MAX_EXPIRING_DATA: u32 = 1024;
+
+/// Maximum number of authorizations set by one
+/// [`force_set_authorizations`](Pallet::force_set_authorizations) call.
+pub const MAX_FORCED_AUTHORIZATIONS: u32 = 1024;
 
 /// Number of transactions and bytes covered by an authorization.
 #[derive(
@@ -75,11 +136,61 @@ pub struct AuthorizationExtent {
 	pub bytes: u64,
 }
 
+/// Account authorization set by
+/// [`force_set_authorizations`](Pallet::force_set_authorizations).
+#[derive(
+	Clone,
+	PartialEq,
+	Eq,
+	sp_runtime::RuntimeDebug,
+	Encode,
+	Decode,
+	scale_info::TypeInfo,
+	MaxEncodedLen,
+)]
+pub struct AuthorizationDetails<BlockNumber> {
+	/// Number of transactions the account may submit.
+	pub transactions: u32,
+	/// Number of bytes the account may store.
+	pub bytes: u64,
+	/// The block at which the authorization expires.
+	pub expiration: BlockNumber,
+}
+
+/// Remaining storage authorization of an account. See
+/// [`account_authorization_status`](Pallet::account_authorization_status).
+#[derive(Clone, PartialEq, Eq, sp_runtime::RuntimeDebug, Encode, Decode, scale_info::TypeInfo)]
+pub struct AuthorizationStatus<BlockNumber> {
+	/// Number of bytes the account may still store.
+	pub bytes_left: u64,
+	/// Number of transactions the account may still submit.
+	pub transactions_left: u32,
+	/// The block at which the authorization expires.
+	pub expires_at: BlockNumber,
+}
+
 /// Hash of a stored blob of data.
 type ContentHash = [u8; 32];
 
+/// Binary CIDv1 of a stored blob of data, using the raw codec and a BLAKE2b-256 multihash of the
+/// content hash. Data is served over IPFS Bitswap by nodes running with `--ipfs-server` under
+/// this CID.
+pub type Cid = [u8; 38];
+
+/// Prefix of every [`Cid`]: CIDv1, raw codec (`0x55`), BLAKE2b-256 multihash (`0xb220`, varint
+/// encoded) with a 32-byte digest.
+const CID_PREFIX: [u8; 6] = [0x01, 0x55, 0xa0, 0xe4, 0x02, 0x20];
+
+/// Returns the [`Cid`] of data with the given BLAKE2b-256 hash.
+pub fn cid(content_hash: &[u8; 32]) -> Cid {
+	let mut cid = [0; 38];
+	cid[..CID_PREFIX.len()].copy_from_slice(&CID_PREFIX);
+	cid[CID_PREFIX.len()..].copy_from_slice(content_hash);
+	cid
+}
+
 /// The scope of an authorization.
-#[derive(Encode, Decode, scale_info::TypeInfo, MaxEncodedLen)]
+#[derive(Clone, Encode, Decode, scale_info::TypeInfo, MaxEncodedLen)]
 enum AuthorizationScope<AccountId> {
 	/// Authorization for the given account to store arbitrary data.
 	Account(AccountId),
@@ -100,7 +211,188 @@ struct Authorization<BlockNumber> {
 
 type AuthorizationFor<T> = Authorization<BlockNumberFor<T>>;
 
-/// State data for a stored transaction.
+/// Limit on how fast an account authorization may be used. See
+/// [`set_authorization_rate`](Pallet::set_authorization_rate).
+#[derive(
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	sp_runtime::RuntimeDebug,
+	Encode,
+	Decode,
+	scale_info::TypeInfo,
+	MaxEncodedLen,
+)]
+pub struct RateLimit<BlockNumber> {
+	/// Maximum number of bytes that may be used in a single period.
+	pub bytes: u64,
+	/// Length of a period in blocks. Periods start at multiples of this.
+	pub period: BlockNumber,
+}
+
+/// Rate limit of an account authorization and its use in the current period.
+#[derive(Encode, Decode, scale_info::TypeInfo, MaxEncodedLen)]
+struct AuthorizationRate<BlockNumber> {
+	limit: RateLimit<BlockNumber>,
+	/// First block of the period `used` applies to.
+	period_start: BlockNumber,
+	/// Number of bytes used in the period.
+	used: u64,
+}
+
+type AuthorizationRateFor<T> = AuthorizationRate<BlockNumberFor<T>>;
+
+/// Extent an account authorization is refreshed to every period. See
+/// [`set_standing_authorization`](Pallet::set_standing_authorization).
+#[derive(
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	sp_runtime::RuntimeDebug,
+	Encode,
+	Decode,
+	scale_info::TypeInfo,
+	MaxEncodedLen,
+)]
+pub struct StandingQuota<BlockNumber> {
+	/// Number of transactions the account may submit in each period.
+	pub transactions: u32,
+	/// Number of bytes the account may store in each period.
+	pub bytes: u64,
+	/// Length of a period in blocks. Periods start at multiples of this.
+	pub period: BlockNumber,
+}
+
+/// Standing quota of an account and the period it was last applied in.
+#[derive(Encode, Decode, scale_info::TypeInfo, MaxEncodedLen)]
+struct StandingAuthorization<BlockNumber> {
+	quota: StandingQuota<BlockNumber>,
+	/// First block of the period the account authorization was last refreshed in, if any.
+	refreshed: Option<BlockNumber>,
+}
+
+type StandingAuthorizationFor<T> = StandingAuthorization<BlockNumberFor<T>>;
+
+/// Permission for an account to store data using the account authorization of another account.
+/// See [`delegate`](Pallet::delegate).
+#[derive(Encode, Decode, scale_info::TypeInfo, MaxEncodedLen)]
+struct Delegation<AccountId, BlockNumber> {
+	/// The account whose authorization is used.
+	owner: AccountId,
+	/// Maximum number of bytes the delegate may store.
+	bytes_limit: u64,
+	/// Number of bytes the delegate has stored so far.
+	bytes_used: u64,
+	/// The block at which this delegation expires.
+	expiration: BlockNumber,
+}
+
+type DelegationFor<T> = Delegation<<T as frame_system::Config>::AccountId, BlockNumberFor<T>>;
+
+/// Name resolving to stored data, for example `b"latest-runtime"`. See
+/// [`claim_name`](Pallet::claim_name).
+pub type Name = BoundedVec<u8, ConstU32<MAX_NAME_LEN>>;
+
+/// The data a [`Name`] currently points to.
+#[derive(Encode, Decode, scale_info::TypeInfo, MaxEncodedLen)]
+struct NamePointer<AccountId, BlockNumber> {
+	/// The account that claimed the name. Only this account may repoint or release it.
+	owner: AccountId,
+	/// BLAKE2b-256 hash of the data the name points to.
+	content_hash: ContentHash,
+	/// The block in which the name was last pointed.
+	updated: BlockNumber,
+}
+
+type NamePointerFor<T> = NamePointer<<T as frame_system::Config>::AccountId, BlockNumberFor<T>>;
+
+/// Identifier of a chunked upload session.
+pub type UploadSessionId = u32;
+
+/// State of a chunked upload. See [`begin_upload`](Pallet::begin_upload).
+#[derive(Encode, Decode, scale_info::TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(MaxChunks))]
+#[codec(mel_bound(AccountId: MaxEncodedLen, BlockNumber: MaxEncodedLen))]
+struct UploadSession<AccountId, BlockNumber, MaxChunks: Get<u32>> {
+	/// The account that started the upload. Only this account may supply chunks.
+	owner: AccountId,
+	/// Committed root: the ordered BLAKE2b-256 trie root of the chunk hashes.
+	root: ContentHash,
+	/// Declared total size of the upload in bytes.
+	size: u64,
+	/// Declared number of chunks.
+	chunk_count: u32,
+	/// Hashes of the chunks supplied so far, in order.
+	chunk_hashes: BoundedVec<ContentHash, MaxChunks>,
+	/// Number of bytes supplied so far.
+	received: u64,
+	/// The block at which the upload times out, unless another chunk is supplied or it is
+	/// resumed first.
+	expires_at: BlockNumber,
+	/// The block in which the first chunk was stored.
+	first_block: BlockNumber,
+	/// The block in which the most recent chunk was stored.
+	last_block: BlockNumber,
+}
+
+type UploadSessionFor<T> = UploadSession<
+	<T as frame_system::Config>::AccountId,
+	BlockNumberFor<T>,
+	<T as Config>::MaxUploadChunks,
+>;
+
+/// Receipt of a completed chunked upload. See [`finalize_upload`](Pallet::finalize_upload).
+#[derive(
+	Clone,
+	PartialEq,
+	Eq,
+	sp_runtime::RuntimeDebug,
+	Encode,
+	Decode,
+	scale_info::TypeInfo,
+	MaxEncodedLen,
+)]
+pub struct UploadReceipt<BlockNumber> {
+	/// The ordered BLAKE2b-256 trie root of the chunk hashes.
+	pub root: ContentHash,
+	/// Total size of the upload in bytes.
+	pub size: u64,
+	/// Number of chunks.
+	pub chunk_count: u32,
+	/// The block in which the first chunk was stored.
+	pub first_block: BlockNumber,
+	/// The block in which the last chunk was stored.
+	pub last_block: BlockNumber,
+}
+
+/// A completed chunked upload, kept so that chunk proofs can be generated.
+#[derive(Encode, Decode, scale_info::TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(MaxChunks))]
+#[codec(mel_bound(BlockNumber: MaxEncodedLen))]
+struct CompletedUpload<BlockNumber, MaxChunks: Get<u32>> {
+	receipt: UploadReceipt<BlockNumber>,
+	/// Hashes of the chunks, in order.
+	chunk_hashes: BoundedVec<ContentHash, MaxChunks>,
+}
+
+type CompletedUploadFor<T> = CompletedUpload<BlockNumberFor<T>, <T as Config>::MaxUploadChunks>;
+
+/// Proof that a chunk of a completed chunked upload has a given hash. See
+/// [`upload_chunk_proof`](Pallet::upload_chunk_proof).
+#[derive(Clone, PartialEq, Eq, sp_runtime::RuntimeDebug, Encode, Decode, scale_info::TypeInfo)]
+pub struct ChunkProof {
+	/// BLAKE2b-256 hash of the chunk. The chunk itself can be found with
+	/// [`content_location`](Pallet::content_location) while it is being kept.
+	pub content_hash: ContentHash,
+	/// Compact trie proof (state version 1) of `content_hash` under the key `Compact(chunk)` in
+	/// the upload's root. Can be checked with `sp_io::trie::blake2_256_verify_proof`.
+	pub proof: Vec<Vec<u8>>,
+}
+
+/// State data for a stored transaction. The [`TransactionInfo`]s of a block are kept under
+/// [`transactions_key`](Pallet::transactions_key) until the block's data expires.
 #[derive(
 	Encode,
 	Decode,
@@ -113,16 +405,207 @@ type AuthorizationFor<T> = Authorization<BlockNumberFor<T>>;
 )]
 pub struct TransactionInfo {
 	/// Chunk trie root.
-	chunk_root: <BlakeTwo256 as Hash>::Output,
+	pub chunk_root: <BlakeTwo256 as Hash>::Output,
 	/// Plain hash of indexed data.
-	content_hash: <BlakeTwo256 as Hash>::Output,
+	pub content_hash: <BlakeTwo256 as Hash>::Output,
 	/// Size of indexed data in bytes.
-	size: u32,
+	pub size: u32,
 	/// Total number of chunks added in the block with this transaction. This
 	/// is used find transaction info by block chunk index using binary search.
-	block_chunks: u32,
+	pub block_chunks: u32,
+}
+
+/// Storage usage, for tuning budgets. See [`storage_usage`](Pallet::storage_usage).
+#[derive(Clone, PartialEq, Eq, sp_runtime::RuntimeDebug, Encode, Decode, scale_info::TypeInfo)]
+pub struct StorageUsage<BlockNumber> {
+	/// Total size in bytes of the unexpired and unretracted `store`/`renew` transactions.
+	pub total_bytes: u64,
+	/// Number of those bytes expiring in each upcoming `UsagePeriod`, keyed by the first block
+	/// of the period, in ascending order.
+	pub expiring_bytes: Vec<(BlockNumber, u64)>,
+	/// Number of those bytes in each configured namespace, along with the namespace's maximum.
+	pub namespaces: Vec<(Namespace, u64, u64)>,
+}
+
+/// Encoding of data stored with [`store_encoded`](Pallet::store_encoded).
+///
+/// Gzip is deliberately not supported. Its only size field, ISIZE, is the size of the last member
+/// modulo 2^32: earlier members of a multi-member stream are not covered by it, and finding them
+/// requires inflating the data. The declared size could then be far below what decoders produce,
+/// so authorization could not be charged for the decoded size.
+#[derive(
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	sp_runtime::RuntimeDebug,
+	Encode,
+	Decode,
+	scale_info::TypeInfo,
+	MaxEncodedLen,
+)]
+pub enum ContentEncoding {
+	/// The data is not encoded.
+	Identity,
+	/// A single Zstandard frame which declares its content size.
+	Zstd,
+}
+
+/// Availability status of a piece of data. See [`data_status`](Pallet::data_status).
+#[derive(Clone, PartialEq, Eq, sp_runtime::RuntimeDebug, Encode, Decode, scale_info::TypeInfo)]
+pub enum DataStatus<BlockNumber> {
+	/// The data is being kept. The block number is that of its most recent `store` or `renew`.
+	Included(BlockNumber),
+	/// The data expired within the last `StoragePeriod` blocks and is no longer guaranteed to be
+	/// retrievable.
+	Expired,
+	/// The data is not known to the chain.
+	Unknown,
+	/// The data is a chunked upload with the given number of chunks still to be supplied.
+	PendingChunks(u32),
+	/// The data is a completed chunked upload. Its chunks are kept like any other data, until
+	/// `StoragePeriod` blocks after they were stored.
+	Uploaded(UploadReceipt<BlockNumber>),
+}
+
+/// Tag grouping stored data by consumer, for example `b"people-identity"`.
+pub type Namespace = BoundedVec<u8, ConstU32<MAX_NAMESPACE_LEN>>;
+
+/// Governance configuration of a [`Namespace`].
+#[derive(
+	Clone,
+	PartialEq,
+	Eq,
+	sp_runtime::RuntimeDebug,
+	Encode,
+	Decode,
+	scale_info::TypeInfo,
+	MaxEncodedLen,
+)]
+pub struct NamespaceConfig<BlockNumber> {
+	/// Maximum size in bytes of a single item stored in the namespace.
+	pub max_item_size: u32,
+	/// Number of blocks after being stored for which data in the namespace may be renewed.
+	/// Data is always kept for at least `StoragePeriod` blocks.
+	pub retention: BlockNumber,
+	/// Maximum total size in bytes of the unexpired `store`/`renew` transactions in the
+	/// namespace.
+	pub max_total_bytes: u64,
+}
+
+type NamespaceConfigFor<T> = NamespaceConfig<BlockNumberFor<T>>;
+
+/// Provenance metadata for stored data, signed by a key other than that of the submitting
+/// account. See [`store_with_attestation`](Pallet::store_with_attestation).
+#[derive(
+	Clone,
+	PartialEq,
+	Eq,
+	sp_runtime::RuntimeDebug,
+	Encode,
+	Decode,
+	scale_info::TypeInfo,
+	MaxEncodedLen,
+)]
+pub struct Attestation {
+	/// Media type of the data, for example `b"application/json"`.
+	pub content_type: BoundedVec<u8, ConstU32<MAX_CONTENT_TYPE_LEN>>,
+	/// Version of the schema the data follows.
+	pub schema_version: u32,
+	/// Account of the attesting key.
+	pub signer: AccountId32,
+	/// Signature by `signer` over [`message`](Self::message).
+	pub signature: MultiSignature,
+}
+
+impl Attestation {
+	/// Returns the message signed to attest to data with the given content hash: the SCALE
+	/// encoding of the content hash, content type and schema version.
+	pub fn message(&self, content_hash: &[u8; 32]) -> Vec<u8> {
+		(content_hash, &self.content_type, self.schema_version).encode()
+	}
+
+	/// Returns `true` if the signature is valid for data with the given content hash.
+	pub fn verify(&self, content_hash: &[u8; 32]) -> bool {
+		self.signature.verify(&self.message(content_hash)[..], &self.signer)
+	}
+}
+
+/// Handler for data that is no longer kept by the chain, for example to let other pallets drop
+/// their references to it.
+pub trait OnDataExpired<AccountId> {
+	/// Called in `on_initialize` when the last unexpired `store`/`renew` transaction of the data
+	/// with the given hash expires. `namespace` and `uploader` are the namespace and signed
+	/// account, if any, of the expiring transaction.
+	///
+	/// Not called for data that was retracted. Returns the weight consumed.
+	fn on_data_expired(
+		content_hash: &[u8; 32],
+		namespace: Option<&Namespace>,
+		uploader: Option<&AccountId>,
+	) -> Weight;
+}
+
+impl<AccountId> OnDataExpired<AccountId> for () {
+	fn on_data_expired(_: &[u8; 32], _: Option<&Namespace>, _: Option<&AccountId>) -> Weight {
+		Weight::zero()
+	}
+}
+
+/// Validator of the data stored in namespaces, for example requiring the data in a namespace to
+/// be a valid SCALE encoding of some type, or a Wasm blob. Namespaces that a validator does not
+/// handle should accept any data.
+pub trait ValidatePayload {
+	/// Returns `true` if `data` may be stored in `namespace`. Called on dispatch, after the
+	/// transaction has been charged, so should be cheap relative to the weight of storing `data`.
+	fn validate_payload(namespace: &Namespace, data: &[u8]) -> bool;
+}
+
+impl ValidatePayload for () {
+	fn validate_payload(_: &Namespace, _: &[u8]) -> bool {
+		true
+	}
+}
+
+/// Content index entry for a piece of stored data.
+#[derive(
+	Encode, Decode, sp_runtime::RuntimeDebug, PartialEq, Eq, scale_info::TypeInfo, MaxEncodedLen,
+)]
+struct ContentEntry<BlockNumber> {
+	/// Block number of the most recent `store` or `renew` of the data.
+	block: BlockNumber,
+	/// Index of the most recent `store` or `renew` of the data within `block`.
+	index: u32,
+	/// Number of unexpired `store`/`renew` transactions for the data. As identical data is
+	/// indexed under the same hash, it is only kept once by nodes.
+	refs: u32,
+	/// Namespace the data was most recently stored in. A later `store` without a namespace
+	/// doesn't change this.
+	namespace: Option<Namespace>,
+	/// Block number of the most recent `store` of the data into its namespace (or of any `store`
+	/// if it has none). Renewals don't change this.
+	stored_at: BlockNumber,
+}
+
+type ContentEntryFor<T> = ContentEntry<BlockNumberFor<T>>;
+
+/// Block number and index of a `store` or `renew` of some data.
+type CopyLocation<BlockNumber> = (BlockNumber, u32);
+
+/// The neighbours of a `store` or `renew` in the list of unexpired and unretracted copies of some
+/// data, which is ordered from oldest to most recent.
+#[derive(
+	Encode, Decode, sp_runtime::RuntimeDebug, PartialEq, Eq, scale_info::TypeInfo, MaxEncodedLen,
+)]
+struct CopyLinks<BlockNumber> {
+	/// The next older copy, if any.
+	prev: Option<CopyLocation<BlockNumber>>,
+	/// The next more recent copy, if any.
+	next: Option<CopyLocation<BlockNumber>>,
 }
 
+type CopyLinksFor<T> = CopyLinks<BlockNumberFor<T>>;
+
 /// Context of a `check_signed`/`check_unsigned` call.
 #[derive(Clone, Copy)]
 enum CheckContext {
@@ -183,12 +666,42 @@ pub mod pallet {
 		/// Longevity of store/renew transactions.
 		#[pallet::constant]
 		type StoreRenewLongevity: Get<TransactionLongevity>;
+		/// Priority of `store_operational` transactions. Should be higher than
+		/// `StoreRenewPriority`.
+		#[pallet::constant]
+		type OperationalStorePriority: Get<TransactionPriority>;
 		/// Priority of unsigned transactions to remove expired authorizations.
 		#[pallet::constant]
 		type RemoveExpiredAuthorizationPriority: Get<TransactionPriority>;
 		/// Longevity of unsigned transactions to remove expired authorizations.
 		#[pallet::constant]
 		type RemoveExpiredAuthorizationLongevity: Get<TransactionLongevity>;
+		/// Maximum number of chunks in a chunked upload.
+		#[pallet::constant]
+		type MaxUploadChunks: Get<u32>;
+		/// Maximum total size in bytes of the data stored or renewed in a block. Data stored with
+		/// `store_operational` is not limited by this, but does use it up.
+		#[pallet::constant]
+		type MaxBlockBytes: Get<u32>;
+		/// Chunked uploads time out if no chunk is supplied for this many blocks. A timed out
+		/// upload may be resumed for the same number of blocks again, after which it is removed.
+		#[pallet::constant]
+		type UploadTimeout: Get<BlockNumberFor<Self>>;
+		/// Handler called when stored data expires.
+		type OnDataExpired: OnDataExpired<Self::AccountId>;
+		/// Validator of the data stored with [`store_in_namespace`](Pallet::store_in_namespace).
+		type PayloadValidator: ValidatePayload;
+		/// Length in blocks of the periods expiring bytes are grouped by in
+		/// [`StorageUsage`].
+		#[pallet::constant]
+		type UsagePeriod: Get<BlockNumberFor<Self>>;
+		/// Number of blocks after a [`commit`](Pallet::commit) in which the data may be revealed.
+		#[pallet::constant]
+		type RevealPeriod: Get<BlockNumberFor<Self>>;
+		/// Source of the randomness used to select the chunk proved by each storage proof. It
+		/// must be known when the block containing the proof is built, but should not be
+		/// biasable by its author. If no randomness is available, the parent block hash is used.
+		type Randomness: Randomness<Option<Self::Hash>, BlockNumberFor<Self>>;
 	}
 
 	#[pallet::error]
@@ -213,6 +726,58 @@ pub mod pallet {
 		AuthorizationNotFound,
 		/// Authorization has not expired.
 		AuthorizationNotExpired,
+		/// Upload size or chunk count is not in the allowed range.
+		BadUploadParameters,
+		/// Upload session was not found.
+		UploadNotFound,
+		/// Upload session is owned by a different account.
+		NotUploadOwner,
+		/// Upload chunk is out of order or exceeds the declared upload size.
+		UnexpectedUploadChunk,
+		/// Not all chunks of the upload have been supplied.
+		UploadIncomplete,
+		/// The supplied chunks do not match the committed root.
+		UploadRootMismatch,
+		/// Too many bytes stored or renewed in the block.
+		TooManyBytes,
+		/// Namespace is not configured, or data exceeds its maximum item size or total size.
+		BadNamespace,
+		/// Data has been kept for the full retention period of its namespace.
+		RetentionExceeded,
+		/// Retracted data not found.
+		RetractedNotFound,
+		/// Only the account that stored the data may retract it.
+		NotUploader,
+		/// Upload session has timed out and must be resumed.
+		UploadTimedOut,
+		/// Authorization is not sufficient for the transfer.
+		InsufficientAuthorization,
+		/// Accounts cannot delegate to themselves, and delegations must not already be expired.
+		BadDelegation,
+		/// The account has an unexpired delegation from another account.
+		AlreadyDelegated,
+		/// Delegation not found.
+		DelegationNotFound,
+		/// Attestation signature is not valid for the data.
+		BadAttestation,
+		/// Data is not framed as its declared encoding requires.
+		BadEncoding,
+		/// Name has already been claimed.
+		NameTaken,
+		/// Name has not been claimed by the signer.
+		NotNameOwner,
+		/// Data with the given hash is not being kept.
+		ContentNotFound,
+		/// Rate limit periods must not be empty.
+		BadRateLimit,
+		/// Standing quotas must have transactions, bytes and a non-empty period.
+		BadStandingQuota,
+		/// Data was rejected by the namespace's payload validator.
+		InvalidPayload,
+		/// Data has already been committed to by the signer, or is already being kept.
+		AlreadyCommitted,
+		/// The signer has no commitment to the data, or it has expired.
+		CommitmentNotFound,
 	}
 
 	#[pallet::pallet]
@@ -230,17 +795,71 @@ pub mod pallet {
 			let period = T::StoragePeriod::get();
 			let obsolete = n.saturating_sub(period.saturating_add(One::one()));
 			if obsolete > Zero::zero() {
-				weight.saturating_accrue(db_weight.writes(2));
-				<Transactions<T>>::remove(obsolete);
+				weight.saturating_accrue(db_weight.reads_writes(1, 2));
+				let expired = <Transactions<T>>::take(obsolete);
 				<ChunkCount<T>>::remove(obsolete);
+				let mut released = 0;
+				for (index, info) in expired.into_iter().flatten().enumerate() {
+					let index = index as u32;
+					let content_hash: ContentHash = info.content_hash.into();
+					weight.saturating_accrue(db_weight.reads_writes(7, 9));
+					let uploader = Uploaders::<T>::take(obsolete, index);
+					let namespace = Self::release_namespace_bytes(obsolete, index, info.size);
+					Attestations::<T>::remove(obsolete, index);
+					// Retracted data was already dropped from the content index
+					let retracted = Retracted::<T>::take(obsolete, index).is_some();
+					if !retracted {
+						released += info.size as u64;
+					}
+					if !retracted && Self::unref_content(content_hash, obsolete, index) {
+						ExpiredContent::<T>::insert(content_hash, n);
+						CommitBlocks::<T>::remove(content_hash);
+						weight.saturating_accrue(T::OnDataExpired::on_data_expired(
+							&content_hash,
+							namespace.as_ref(),
+							uploader.as_ref(),
+						));
+					}
+					Self::deposit_event(Event::Expired {
+						block: obsolete,
+						index,
+						content_hash,
+						namespace,
+						uploader,
+					});
+				}
+				if released != 0 {
+					weight.saturating_accrue(db_weight.reads_writes(3, 2));
+					Self::release_usage(n, released);
+				}
 			}
 
 			// For `on_finalize`
-			weight.saturating_accrue(db_weight.reads_writes(2, 2));
+			weight.saturating_accrue(db_weight.reads_writes(5, 5));
 
 			weight
 		}
 
+		fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			let mut weight = Self::remove_expired_authorizations(remaining_weight);
+			weight.saturating_accrue(Self::remove_stale_expired_content(
+				remaining_weight.saturating_sub(weight),
+			));
+			weight.saturating_accrue(Self::remove_abandoned_uploads(
+				remaining_weight.saturating_sub(weight),
+			));
+			weight.saturating_accrue(Self::remove_expired_upload_receipts(
+				remaining_weight.saturating_sub(weight),
+			));
+			weight.saturating_accrue(Self::remove_expired_commitments(
+				remaining_weight.saturating_sub(weight),
+			));
+			weight.saturating_accrue(Self::remove_expired_delegations(
+				remaining_weight.saturating_sub(weight),
+			));
+			weight
+		}
+
 		fn on_finalize(n: BlockNumberFor<T>) {
 			assert!(
 				<ProofChecked<T>>::take() || {
@@ -254,9 +873,12 @@ pub mod pallet {
 			);
 
 			// Insert new transactions
+			<BlockBytes<T>>::kill();
 			let transactions = <BlockTransactions<T>>::take();
 			let total_chunks = transactions.last().map_or(0, |t| t.block_chunks);
 			if total_chunks != 0 {
+				let bytes = transactions.iter().map(|t| t.size as u64).sum();
+				Self::note_usage(Self::expiry(n), bytes);
 				<ChunkCount<T>>::insert(n, total_chunks);
 				<Transactions<T>>::insert(n, transactions);
 			}
@@ -273,6 +895,20 @@ pub mod pallet {
 				!T::AuthorizationPeriod::get().is_zero(),
 				"Not useful if authorizations are never valid"
 			);
+			assert!(!T::MaxUploadChunks::get().is_zero(), "Not useful if uploads cannot be made");
+			assert!(
+				!T::UploadTimeout::get().is_zero(),
+				"Not useful if uploads time out immediately"
+			);
+			assert!(!T::UsagePeriod::get().is_zero(), "Usage periods must not be empty");
+			assert!(
+				!T::RevealPeriod::get().is_zero(),
+				"Not useful if commitments cannot be revealed"
+			);
+			assert!(
+				T::MaxBlockBytes::get() >= T::MaxTransactionSize::get(),
+				"Transactions of the maximum size must fit in a block"
+			);
 		}
 	}
 
@@ -280,12 +916,14 @@ pub mod pallet {
 	impl<T: Config> Pallet<T> {
 		/// Index and store data off chain. Minimum data size is 1 bytes, maximum is
 		/// `MaxTransactionSize`. Data will be removed after `StoragePeriod` blocks, unless `renew`
-		/// is called.
+		/// is called. [`Expired`](Event::Expired) is emitted when this happens.
 		///
 		/// Authorization is required to store data using regular signed/unsigned transactions.
 		/// Regular signed transactions require account authorization (see
-		/// [`authorize_account`](Self::authorize_account)), regular unsigned transactions require
-		/// preimage authorization (see [`authorize_preimage`](Self::authorize_preimage)).
+		/// [`authorize_account`](Self::authorize_account)) or preimage authorization, regular
+		/// unsigned transactions require preimage authorization (see
+		/// [`authorize_preimage`](Self::authorize_preimage)). Preimage authorization is used in
+		/// preference to account authorization.
 		///
 		/// Emits [`Stored`](Event::Stored) when successful.
 		///
@@ -294,38 +932,15 @@ pub mod pallet {
 		/// O(n*log(n)) of data size, as all data is pushed to an in-memory trie.
 		#[pallet::call_index(0)]
 		#[pallet::weight(T::WeightInfo::store(data.len() as u32))]
-		pub fn store(_origin: OriginFor<T>, data: Vec<u8>) -> DispatchResult {
+		pub fn store(origin: OriginFor<T>, data: Vec<u8>) -> DispatchResult {
 			// In the case of a regular unsigned transaction, this should have been checked by
 			// pre_dispatch. In the case of a regular signed transaction, this should have been
 			// checked by pre_dispatch_signed.
 			ensure!(Self::data_size_ok(data.len()), Error::<T>::BadDataSize);
 
-			// Chunk data and compute storage root
-			let chunks: Vec<_> = data.chunks(CHUNK_SIZE).map(|c| c.to_vec()).collect();
-			let chunk_count = chunks.len();
-			debug_assert_eq!(chunk_count, num_chunks(data.len() as u32) as usize);
-			let root = sp_io::trie::blake2_256_ordered_root(chunks, sp_runtime::StateVersion::V1);
-
-			let extrinsic_index =
-				<frame_system::Pallet<T>>::extrinsic_index().ok_or(Error::<T>::BadContext)?;
-			let content_hash = sp_io::hashing::blake2_256(&data);
-			sp_io::transaction_index::index(extrinsic_index, data.len() as u32, content_hash);
-
-			let mut index = 0;
-			<BlockTransactions<T>>::mutate(|transactions| {
-				let total_chunks =
-					transactions.last().map_or(0, |t| t.block_chunks) + (chunk_count as u32);
-				index = transactions.len() as u32;
-				transactions
-					.try_push(TransactionInfo {
-						chunk_root: root,
-						size: data.len() as u32,
-						content_hash: content_hash.into(),
-						block_chunks: total_chunks,
-					})
-					.map_err(|_| Error::<T>::TooManyTransactions)
-			})?;
-			Self::deposit_event(Event::Stored { index });
+			let (index, content_hash) = Self::do_store(&data, None, DispatchClass::Normal)?;
+			Self::note_uploader(index, ensure_signed(origin).ok());
+			Self::deposit_event(Event::Stored { index, cid: cid(&content_hash), namespace: None });
 			Ok(())
 		}
 
@@ -344,7 +959,7 @@ pub mod pallet {
 		#[pallet::call_index(1)]
 		#[pallet::weight(T::WeightInfo::renew())]
 		pub fn renew(
-			_origin: OriginFor<T>,
+			origin: OriginFor<T>,
 			block: BlockNumberFor<T>,
 			index: u32,
 		) -> DispatchResultWithPostInfo {
@@ -354,9 +969,16 @@ pub mod pallet {
 			// pre_dispatch. In the case of a regular signed transaction, this should have been
 			// checked by pre_dispatch_signed.
 			ensure!(Self::data_size_ok(info.size as usize), Error::<T>::BadDataSize);
+			ensure!(Self::renewal_allowed(info.content_hash.into()), Error::<T>::RetentionExceeded);
+			ensure!(
+				Self::renewal_has_room(info.content_hash.into(), info.size as usize),
+				Error::<T>::BadNamespace
+			);
+			let namespace = Self::renewal_namespace(info.content_hash.into());
 
 			let extrinsic_index =
 				<frame_system::Pallet<T>>::extrinsic_index().ok_or(Error::<T>::BadContext)?;
+			Self::note_block_bytes(info.size, DispatchClass::Normal)?;
 			sp_io::transaction_index::renew(extrinsic_index, info.content_hash.into());
 
 			let mut index = 0;
@@ -373,6 +995,11 @@ pub mod pallet {
 					})
 					.map_err(|_| Error::<T>::TooManyTransactions)
 			})?;
+			Self::note_renewed(info.content_hash.into(), index);
+			if let Some(namespace) = namespace {
+				Self::note_namespace_bytes(index, namespace, info.size);
+			}
+			Self::note_uploader(index, ensure_signed(origin).ok());
 			Self::deposit_event(Event::Renewed { index });
 			Ok(().into())
 		}
@@ -398,8 +1025,9 @@ pub mod pallet {
 			ensure!(!target_number.is_zero(), Error::<T>::UnexpectedProof);
 			let total_chunks = <ChunkCount<T>>::get(target_number);
 			ensure!(total_chunks != 0, Error::<T>::UnexpectedProof);
-			let parent_hash = <frame_system::Pallet<T>>::parent_hash();
-			let selected_chunk_index = random_chunk(parent_hash.as_ref(), total_chunks);
+			let random_hash =
+				Self::proof_randomness().unwrap_or_else(<frame_system::Pallet<T>>::parent_hash);
+			let selected_chunk_index = random_chunk(random_hash.as_ref(), total_chunks);
 			let (info, chunk_index) = match <Transactions<T>>::get(target_number) {
 				Some(infos) => {
 					let index = match infos
@@ -486,7 +1114,8 @@ pub mod pallet {
 			Ok(())
 		}
 
-		/// Remove an expired account authorization from storage. Anyone can call this.
+		/// Remove an expired account authorization from storage. Anyone can call this. Expired
+		/// authorizations are also removed automatically in blocks with spare weight.
 		///
 		/// Parameters:
 		///
@@ -505,7 +1134,8 @@ pub mod pallet {
 			Ok(())
 		}
 
-		/// Remove an expired preimage authorization from storage. Anyone can call this.
+		/// Remove an expired preimage authorization from storage. Anyone can call this. Expired
+		/// authorizations are also removed automatically in blocks with spare weight.
 		///
 		/// Parameters:
 		///
@@ -524,47 +1154,894 @@ pub mod pallet {
 			Self::deposit_event(Event::ExpiredPreimageAuthorizationRemoved { hash });
 			Ok(())
 		}
-	}
-
-	#[pallet::event]
-	#[pallet::generate_deposit(pub(super) fn deposit_event)]
-	pub enum Event<T: Config> {
-		/// Stored data under specified index.
-		Stored { index: u32 },
-		/// Renewed data under specified index.
-		Renewed { index: u32 },
-		/// Storage proof was successfully checked.
-		ProofChecked,
-		/// An account `who` was authorized to store `bytes` bytes in `transactions` transactions.
-		AccountAuthorized { who: T::AccountId, transactions: u32, bytes: u64 },
-		/// Authorization was given for a preimage of `hash` (not exceeding `max_size`) to be
-		/// stored by anyone.
-		PreimageAuthorized { hash: ContentHash, max_size: u64 },
-		/// An expired account authorization was removed.
-		ExpiredAccountAuthorizationRemoved { who: T::AccountId },
-		/// An expired preimage authorization was removed.
-		ExpiredPreimageAuthorizationRemoved { hash: ContentHash },
-	}
 
-	/// Authorizations, keyed by scope.
-	#[pallet::storage]
-	pub(super) type Authorizations<T: Config> =
-		StorageMap<_, Blake2_128Concat, AuthorizationScopeFor<T>, AuthorizationFor<T>, OptionQuery>;
+		/// Start a chunked upload of data that is too large to be stored in a single transaction.
+		/// The chunks are then supplied in order with [`upload_chunk`](Self::upload_chunk), across
+		/// as many blocks as needed, and the upload is completed with
+		/// [`finalize_upload`](Self::finalize_upload).
+		///
+		/// Each chunk is indexed and retained exactly like data stored with
+		/// [`store`](Self::store), and requires the same account authorization. Starting an upload
+		/// uses one transaction of the signer's account authorization, and the signer must be
+		/// authorized to store `size` bytes in a further `chunk_count` transactions.
+		///
+		/// The upload times out if no chunk is supplied for `UploadTimeout` blocks, and must then
+		/// be continued with [`resume_upload`](Self::resume_upload). Uploads that are not resumed
+		/// within a further `UploadTimeout` blocks are removed; chunks already supplied are kept
+		/// until they expire as usual.
+		///
+		/// Parameters:
+		///
+		/// - `root`: The ordered BLAKE2b-256 trie root (state version 1) of the BLAKE2b-256 hashes
+		///   of the chunks.
+		/// - `size`: The total size, in bytes, of the data.
+		/// - `chunk_count`: The number of chunks the data is split into. Must not exceed
+		///   `MaxUploadChunks`.
+		///
+		/// Emits [`UploadStarted`](Event::UploadStarted) when successful.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::begin_upload())]
+		pub fn begin_upload(
+			origin: OriginFor<T>,
+			root: ContentHash,
+			size: u64,
+			chunk_count: u32,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::upload_parameters_ok(size, chunk_count), Error::<T>::BadUploadParameters);
 
-	/// Collection of transaction metadata by block number.
-	#[pallet::storage]
-	#[pallet::getter(fn transaction_roots)]
-	pub(super) type Transactions<T: Config> = StorageMap<
-		_,
-		Blake2_128Concat,
-		BlockNumberFor<T>,
-		BoundedVec<TransactionInfo, T::MaxBlockTransactions>,
-		OptionQuery,
-	>;
+			let session = NextUploadSessionId::<T>::mutate(|id| {
+				let session = *id;
+				*id = id.wrapping_add(1);
+				session
+			});
+			UploadSessions::<T>::insert(
+				session,
+				UploadSession {
+					owner: who.clone(),
+					root,
+					size,
+					chunk_count,
+					chunk_hashes: BoundedVec::new(),
+					received: 0,
+					expires_at: Self::upload_expiry(),
+					first_block: frame_system::Pallet::<T>::block_number(),
+					last_block: frame_system::Pallet::<T>::block_number(),
+				},
+			);
+			Self::deposit_event(Event::UploadStarted { session, who, root, size, chunk_count });
+			Ok(())
+		}
 
-	/// Count indexed chunks for each block.
-	#[pallet::storage]
-	pub(super) type ChunkCount<T: Config> =
+		/// Supply the next chunk of a chunked upload started with
+		/// [`begin_upload`](Self::begin_upload). Chunks must be supplied in order; `chunk` is the
+		/// zero-based position of `data` within the upload.
+		///
+		/// The origin must be the account that started the upload, and the upload must not have
+		/// timed out. Supplying a chunk restarts the timeout. Emits
+		/// [`UploadChunkStored`](Event::UploadChunkStored) when successful.
+		///
+		/// ## Complexity
+		///
+		/// O(n*log(n)) of data size, as all data is pushed to an in-memory trie.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::upload_chunk(data.len() as u32))]
+		pub fn upload_chunk(
+			origin: OriginFor<T>,
+			session: UploadSessionId,
+			chunk: u32,
+			data: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::data_size_ok(data.len()), Error::<T>::BadDataSize);
+
+			let mut upload = UploadSessions::<T>::get(session).ok_or(Error::<T>::UploadNotFound)?;
+			ensure!(upload.owner == who, Error::<T>::NotUploadOwner);
+			ensure!(!Self::expired(upload.expires_at), Error::<T>::UploadTimedOut);
+			ensure!(
+				Self::upload_chunk_ok(&upload, chunk, data.len()),
+				Error::<T>::UnexpectedUploadChunk
+			);
+
+			let (index, content_hash) = Self::do_store(&data, None, DispatchClass::Normal)?;
+			Self::note_uploader(index, Some(who));
+			let now = frame_system::Pallet::<T>::block_number();
+			if chunk == 0 {
+				upload.first_block = now;
+			}
+			upload.last_block = now;
+			upload
+				.chunk_hashes
+				.try_push(content_hash)
+				.map_err(|_| Error::<T>::UnexpectedUploadChunk)?;
+			upload.received.saturating_accrue(data.len() as u64);
+			upload.expires_at = Self::upload_expiry();
+			UploadSessions::<T>::insert(session, upload);
+			Self::deposit_event(Event::UploadChunkStored { session, chunk, index });
+			Ok(())
+		}
+
+		/// Complete a chunked upload. All chunks must have been supplied and their hashes must
+		/// match the root committed to in [`begin_upload`](Self::begin_upload).
+		///
+		/// The origin must be the account that started the upload. Emits
+		/// [`UploadFinalized`](Event::UploadFinalized) with a receipt of the upload when
+		/// successful. The receipt and the chunk hashes are kept until the data of the last chunk
+		/// expires, so that proofs of individual chunks can be generated with
+		/// [`upload_chunk_proof`](Pallet::upload_chunk_proof).
+		///
+		/// ## Complexity
+		///
+		/// O(n*log(n)) of the number of chunks.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::finalize_upload(T::MaxUploadChunks::get()))]
+		pub fn finalize_upload(
+			origin: OriginFor<T>,
+			session: UploadSessionId,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let upload = UploadSessions::<T>::get(session).ok_or(Error::<T>::UploadNotFound)?;
+			ensure!(upload.owner == who, Error::<T>::NotUploadOwner);
+			ensure!(Self::upload_complete(&upload), Error::<T>::UploadIncomplete);
+
+			let hashes = upload.chunk_hashes.iter().map(|hash| hash.to_vec()).collect();
+			let root: ContentHash =
+				sp_io::trie::blake2_256_ordered_root(hashes, sp_runtime::StateVersion::V1).into();
+			ensure!(root == upload.root, Error::<T>::UploadRootMismatch);
+
+			UploadSessions::<T>::remove(session);
+			let chunk_count = upload.chunk_count;
+			let receipt = UploadReceipt {
+				root,
+				size: upload.size,
+				chunk_count: upload.chunk_count,
+				first_block: upload.first_block,
+				last_block: upload.last_block,
+			};
+			UploadReceipts::<T>::insert(
+				root,
+				CompletedUpload { receipt: receipt.clone(), chunk_hashes: upload.chunk_hashes },
+			);
+			Self::deposit_event(Event::UploadFinalized { session, receipt });
+			Ok(Some(T::WeightInfo::finalize_upload(chunk_count)).into())
+		}
+
+		/// Index and store data off chain, tagged with `namespace`. As [`store`](Self::store), but
+		/// `namespace` must have been configured with
+		/// [`set_namespace_config`](Self::set_namespace_config), and the data must not exceed the
+		/// namespace's maximum item size, nor take the namespace over its maximum total size.
+		/// Renewals of the data are limited by the namespace's retention period, and also count
+		/// towards its total size.
+		///
+		/// Emits [`Stored`](Event::Stored) when successful.
+		///
+		/// ## Complexity
+		///
+		/// O(n*log(n)) of data size, as all data is pushed to an in-memory trie.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::store_in_namespace(data.len() as u32))]
+		pub fn store_in_namespace(
+			origin: OriginFor<T>,
+			namespace: Namespace,
+			data: Vec<u8>,
+		) -> DispatchResult {
+			// In the case of a regular unsigned transaction, this should have been checked by
+			// pre_dispatch. In the case of a regular signed transaction, this should have been
+			// checked by pre_dispatch_signed.
+			ensure!(Self::data_size_ok(data.len()), Error::<T>::BadDataSize);
+			ensure!(Self::namespace_ok(&namespace, data.len()), Error::<T>::BadNamespace);
+			ensure!(
+				T::PayloadValidator::validate_payload(&namespace, &data),
+				Error::<T>::InvalidPayload
+			);
+
+			let (index, content_hash) =
+				Self::do_store(&data, Some(namespace.clone()), DispatchClass::Normal)?;
+			Self::note_uploader(index, ensure_signed(origin).ok());
+			Self::deposit_event(Event::Stored {
+				index,
+				cid: cid(&content_hash),
+				namespace: Some(namespace),
+			});
+			Ok(())
+		}
+
+		/// Set or remove the configuration of a namespace. Data can only be stored in configured
+		/// namespaces.
+		///
+		/// Parameters:
+		///
+		/// - `namespace`: The namespace to configure.
+		/// - `config`: The new configuration, or `None` to stop accepting data in the namespace.
+		///
+		/// The origin for this call must be the pallet's `Authorizer`. Emits
+		/// [`NamespaceConfigSet`](Event::NamespaceConfigSet) when successful.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::set_namespace_config())]
+		pub fn set_namespace_config(
+			origin: OriginFor<T>,
+			namespace: Namespace,
+			config: Option<NamespaceConfigFor<T>>,
+		) -> DispatchResult {
+			T::Authorizer::ensure_origin(origin)?;
+			NamespaceConfigs::<T>::set(&namespace, config.clone());
+			Self::deposit_event(Event::NamespaceConfigSet { namespace, config });
+			Ok(())
+		}
+
+		/// Retract data before it expires. Parameters are the block number and transaction index
+		/// of a `store` or `renew` of the data, as for [`renew`](Self::renew).
+		///
+		/// The data is removed from the content index and can no longer be renewed. Nodes must
+		/// still keep it until it expires, as storage proofs for its block cover it. Other
+		/// `store`/`renew` transactions of the same data are not affected; if the most recent one
+		/// is retracted, the content index points to the most recent remaining one instead.
+		///
+		/// The origin for this call must be the pallet's `Authorizer`, or the signed account that
+		/// submitted the `store`/`renew`. Emits [`Retracted`](Event::Retracted) when successful.
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::retract())]
+		pub fn retract(
+			origin: OriginFor<T>,
+			block: BlockNumberFor<T>,
+			index: u32,
+		) -> DispatchResult {
+			if let Err(origin) = T::Authorizer::try_origin(origin) {
+				let who = ensure_signed(origin)?;
+				ensure!(Uploaders::<T>::get(block, index) == Some(who), Error::<T>::NotUploader);
+			}
+			let info = Self::transaction_info(block, index).ok_or(Error::<T>::RetractedNotFound)?;
+
+			let content_hash = info.content_hash.into();
+			Retracted::<T>::insert(block, index, ());
+			Self::release_namespace_bytes(block, index, info.size);
+			Self::release_usage(Self::expiry(block), info.size as u64);
+			if Self::unref_content(content_hash, block, index) {
+				CommitBlocks::<T>::remove(content_hash);
+			}
+			Self::deposit_event(Event::Retracted { block, index, content_hash });
+			Ok(())
+		}
+
+		/// Restart the timeout of a chunked upload, so that it can be continued after missing
+		/// blocks. Timed out uploads can be resumed until they are removed, `UploadTimeout` blocks
+		/// after timing out.
+		///
+		/// The origin must be the account that started the upload. Emits
+		/// [`UploadResumed`](Event::UploadResumed) when successful.
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::resume_upload())]
+		pub fn resume_upload(origin: OriginFor<T>, session: UploadSessionId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut upload = UploadSessions::<T>::get(session).ok_or(Error::<T>::UploadNotFound)?;
+			ensure!(upload.owner == who, Error::<T>::NotUploadOwner);
+
+			let expires_at = Self::upload_expiry();
+			upload.expires_at = expires_at;
+			UploadSessions::<T>::insert(session, upload);
+			Self::deposit_event(Event::UploadResumed { session, expires_at });
+			Ok(())
+		}
+
+		/// Index and store operational data off chain, such as governance artifacts. As
+		/// [`store`](Self::store), but preimage authorization is required, the transaction is
+		/// operational and has priority `OperationalStorePriority`, and the data is not limited
+		/// by `MaxBlockBytes`, so it can't be crowded out by bulk uploads.
+		///
+		/// Emits [`Stored`](Event::Stored) when successful.
+		///
+		/// ## Complexity
+		///
+		/// O(n*log(n)) of data size, as all data is pushed to an in-memory trie.
+		#[pallet::call_index(14)]
+		#[pallet::weight((
+			T::WeightInfo::store_operational(data.len() as u32),
+			DispatchClass::Operational
+		))]
+		pub fn store_operational(origin: OriginFor<T>, data: Vec<u8>) -> DispatchResult {
+			// In the case of a regular unsigned transaction, this should have been checked by
+			// pre_dispatch. In the case of a regular signed transaction, this should have been
+			// checked by pre_dispatch_signed.
+			ensure!(Self::data_size_ok(data.len()), Error::<T>::BadDataSize);
+
+			let (index, content_hash) = Self::do_store(&data, None, DispatchClass::Operational)?;
+			Self::note_uploader(index, ensure_signed(origin).ok());
+			Self::deposit_event(Event::Stored { index, cid: cid(&content_hash), namespace: None });
+			Ok(())
+		}
+
+		/// Transfer part of the signer's unused account authorization to another account, for
+		/// example to split one authorization between several uploader keys.
+		///
+		/// Parameters:
+		///
+		/// - `dest`: The account to be credited with the authorization.
+		/// - `transactions`: The number of transactions to transfer.
+		/// - `bytes`: The number of bytes to transfer.
+		///
+		/// If `dest` has no unexpired authorization, the transferred authorization expires with
+		/// the signer's. Otherwise it is added to `dest`'s authorization, which keeps its
+		/// expiration block.
+		///
+		/// Emits [`AuthorizationTransferred`](Event::AuthorizationTransferred) when successful.
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::transfer_quota())]
+		pub fn transfer_quota(
+			origin: OriginFor<T>,
+			dest: T::AccountId,
+			transactions: u32,
+			bytes: u64,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let scope = AuthorizationScope::Account(who.clone());
+			let expiration = Self::refreshed_authorization(&scope)
+				.map(|authorization| authorization.expiration)
+				.ok_or(Error::<T>::AuthorizationNotFound)?;
+			Self::check_authorization(scope, transactions, bytes, true)
+				.map_err(|_| Error::<T>::InsufficientAuthorization)?;
+			Self::credit_account(dest.clone(), transactions, bytes, expiration);
+			Self::deposit_event(Event::AuthorizationTransferred {
+				from: who,
+				to: dest,
+				transactions,
+				bytes,
+			});
+			Ok(())
+		}
+
+		/// Permit `who` to store data using the signer's account authorization, without
+		/// transferring it. The signer must have an unexpired account authorization.
+		///
+		/// Parameters:
+		///
+		/// - `who`: The account to delegate to.
+		/// - `bytes_limit`: The maximum number of bytes `who` may store using the delegation.
+		/// - `expiration`: The block at which the delegation expires.
+		///
+		/// `who` uses its own account authorization when it is sufficient, and the delegation
+		/// otherwise. An account can only be delegated to by one account at a time. Replacing a
+		/// delegation from the same account keeps the number of bytes already stored using it.
+		/// Expired delegations are removed in blocks with spare weight.
+		///
+		/// Emits [`Delegated`](Event::Delegated) when successful.
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::delegate())]
+		pub fn delegate(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			bytes_limit: u64,
+			expiration: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			ensure!(owner != who && !Self::expired(expiration), Error::<T>::BadDelegation);
+			Self::check_authorization(AuthorizationScope::Account(owner.clone()), 0, 0, false)
+				.map_err(|_| Error::<T>::AuthorizationNotFound)?;
+			let existed = Delegations::<T>::try_mutate(&who, |maybe_delegation| {
+				let existed = maybe_delegation.is_some();
+				let bytes_used = match maybe_delegation {
+					Some(delegation) if delegation.owner == owner => delegation.bytes_used,
+					Some(delegation) if !Self::expired(delegation.expiration) =>
+						return Err(Error::<T>::AlreadyDelegated),
+					_ => 0,
+				};
+				*maybe_delegation =
+					Some(Delegation { owner: owner.clone(), bytes_limit, bytes_used, expiration });
+				Ok(existed)
+			})?;
+			if !existed {
+				frame_system::Pallet::<T>::inc_providers(&who);
+			}
+			Self::deposit_event(Event::Delegated { owner, delegate: who, bytes_limit, expiration });
+			Ok(())
+		}
+
+		/// Revoke the delegation to `who`. May be called by either the account that delegated or
+		/// `who` itself.
+		///
+		/// Parameters:
+		///
+		/// - `who`: The account the delegation was made to.
+		///
+		/// Emits [`DelegationRevoked`](Event::DelegationRevoked) when successful.
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::revoke_delegation())]
+		pub fn revoke_delegation(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			let signer = ensure_signed(origin)?;
+			let Some(delegation) = Delegations::<T>::get(&who) else {
+				return Err(Error::<T>::DelegationNotFound.into())
+			};
+			ensure!(signer == delegation.owner || signer == who, Error::<T>::DelegationNotFound);
+			Delegations::<T>::remove(&who);
+			Self::release_account(&who);
+			Self::deposit_event(Event::DelegationRevoked {
+				owner: delegation.owner,
+				delegate: who,
+			});
+			Ok(())
+		}
+
+		/// Index and store data off chain, with provenance metadata. As [`store`](Self::store),
+		/// but `attestation` must be signed for the data, and is kept alongside it until it
+		/// expires. Renewals of the data do not carry the attestation.
+		///
+		/// Emits [`Stored`](Event::Stored) and then [`Attested`](Event::Attested) when
+		/// successful.
+		///
+		/// ## Complexity
+		///
+		/// O(n*log(n)) of data size, as all data is pushed to an in-memory trie.
+		#[pallet::call_index(18)]
+		#[pallet::weight(T::WeightInfo::store_with_attestation(data.len() as u32))]
+		pub fn store_with_attestation(
+			origin: OriginFor<T>,
+			attestation: Attestation,
+			data: Vec<u8>,
+		) -> DispatchResult {
+			// In the case of a regular unsigned transaction, this should have been checked by
+			// pre_dispatch. In the case of a regular signed transaction, this should have been
+			// checked by pre_dispatch_signed.
+			ensure!(Self::data_size_ok(data.len()), Error::<T>::BadDataSize);
+			ensure!(
+				attestation.verify(&sp_io::hashing::blake2_256(&data)),
+				Error::<T>::BadAttestation
+			);
+
+			let (index, content_hash) = Self::do_store(&data, None, DispatchClass::Normal)?;
+			Self::note_uploader(index, ensure_signed(origin).ok());
+			let block = <frame_system::Pallet<T>>::block_number();
+			Attestations::<T>::insert(block, index, attestation.clone());
+			Self::deposit_event(Event::Stored { index, cid: cid(&content_hash), namespace: None });
+			Self::deposit_event(Event::Attested { index, attestation });
+			Ok(())
+		}
+
+		/// Index and store encoded data off chain. As [`store`](Self::store), but the data is
+		/// declared to be compressed with `encoding`. The decoded size is read from the data's
+		/// framing, without decompressing it, and account authorization is charged for the
+		/// larger of the decoded and encoded sizes. Preimage authorization is charged for the
+		/// encoded size, as it is only valid for the authorized data.
+		///
+		/// Emits [`Stored`](Event::Stored) and then [`Encoded`](Event::Encoded) when successful.
+		///
+		/// ## Complexity
+		///
+		/// O(n*log(n)) of data size, as all data is pushed to an in-memory trie.
+		#[pallet::call_index(19)]
+		#[pallet::weight(T::WeightInfo::store_encoded(data.len() as u32))]
+		pub fn store_encoded(
+			origin: OriginFor<T>,
+			encoding: ContentEncoding,
+			data: Vec<u8>,
+		) -> DispatchResult {
+			// In the case of a regular unsigned transaction, this should have been checked by
+			// pre_dispatch. In the case of a regular signed transaction, this should have been
+			// checked by pre_dispatch_signed.
+			ensure!(Self::data_size_ok(data.len()), Error::<T>::BadDataSize);
+			let decoded_size =
+				encoding::decoded_size(encoding, &data).ok_or(Error::<T>::BadEncoding)?;
+
+			let (index, content_hash) = Self::do_store(&data, None, DispatchClass::Normal)?;
+			Self::note_uploader(index, ensure_signed(origin).ok());
+			Self::deposit_event(Event::Stored { index, cid: cid(&content_hash), namespace: None });
+			Self::deposit_event(Event::Encoded { index, encoding, decoded_size });
+			Ok(())
+		}
+
+		/// Claim `name` and point it to the data with the given hash, which must be being kept.
+		/// The signer must have account authorization, of which one transaction and the length
+		/// of the name in bytes are used.
+		///
+		/// The name can then be repointed with [`point_name`](Self::point_name), so that
+		/// consumers can follow it to the latest version of some data.
+		///
+		/// Emits [`NameClaimed`](Event::NameClaimed) when successful.
+		#[pallet::call_index(20)]
+		#[pallet::weight(T::WeightInfo::claim_name())]
+		pub fn claim_name(
+			origin: OriginFor<T>,
+			name: Name,
+			content_hash: ContentHash,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			ensure!(!Names::<T>::contains_key(&name), Error::<T>::NameTaken);
+			ensure!(ContentIndex::<T>::contains_key(content_hash), Error::<T>::ContentNotFound);
+
+			let updated = frame_system::Pallet::<T>::block_number();
+			Names::<T>::insert(&name, NamePointer { owner: owner.clone(), content_hash, updated });
+			frame_system::Pallet::<T>::inc_providers(&owner);
+			Self::deposit_event(Event::NameClaimed { name, owner, content_hash });
+			Ok(())
+		}
+
+		/// Point `name`, claimed by the signer, to the data with the given hash, which must be
+		/// being kept.
+		///
+		/// Emits [`NamePointed`](Event::NamePointed) when successful.
+		#[pallet::call_index(21)]
+		#[pallet::weight(T::WeightInfo::point_name())]
+		pub fn point_name(
+			origin: OriginFor<T>,
+			name: Name,
+			content_hash: ContentHash,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(ContentIndex::<T>::contains_key(content_hash), Error::<T>::ContentNotFound);
+			let previous = Names::<T>::try_mutate(&name, |maybe_pointer| {
+				let pointer = maybe_pointer.as_mut().ok_or(Error::<T>::NotNameOwner)?;
+				ensure!(pointer.owner == who, Error::<T>::NotNameOwner);
+				pointer.updated = frame_system::Pallet::<T>::block_number();
+				Ok::<_, Error<T>>(sp_std::mem::replace(&mut pointer.content_hash, content_hash))
+			})?;
+			Self::deposit_event(Event::NamePointed { name, content_hash, previous });
+			Ok(())
+		}
+
+		/// Release `name`, claimed by the signer, so that it can be claimed again.
+		///
+		/// Emits [`NameReleased`](Event::NameReleased) when successful.
+		#[pallet::call_index(22)]
+		#[pallet::weight(T::WeightInfo::release_name())]
+		pub fn release_name(origin: OriginFor<T>, name: Name) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let owner = Names::<T>::get(&name).map(|pointer| pointer.owner);
+			ensure!(owner.as_ref() == Some(&who), Error::<T>::NotNameOwner);
+			Names::<T>::remove(&name);
+			Self::release_account(&who);
+			Self::deposit_event(Event::NameReleased { name });
+			Ok(())
+		}
+
+		/// Renew the most recent `store` or `renew` of the data with the given hash, using the
+		/// signer's account authorization. As [`renew`](Self::renew), but the data is identified
+		/// by its hash alone, so that data can be kept available without tracking where it was
+		/// last stored.
+		///
+		/// Emits [`Renewed`](Event::Renewed) when successful.
+		#[pallet::call_index(23)]
+		#[pallet::weight(T::WeightInfo::pin())]
+		pub fn pin(origin: OriginFor<T>, content_hash: ContentHash) -> DispatchResultWithPostInfo {
+			ensure_signed(origin.clone())?;
+			let (block, index) =
+				Self::content_location(content_hash).ok_or(Error::<T>::RenewedNotFound)?;
+			Self::renew(origin, block, index)
+		}
+
+		/// Set or clear the total size in bytes of unexpired data at which
+		/// [`UsageThresholdCrossed`](Event::UsageThresholdCrossed) is emitted.
+		///
+		/// The origin for this call must be the pallet's `Authorizer`. Emits
+		/// [`UsageThresholdSet`](Event::UsageThresholdSet) when successful.
+		#[pallet::call_index(24)]
+		#[pallet::weight(T::WeightInfo::set_usage_threshold())]
+		pub fn set_usage_threshold(origin: OriginFor<T>, threshold: Option<u64>) -> DispatchResult {
+			T::Authorizer::ensure_origin(origin)?;
+			UsageThreshold::<T>::set(threshold);
+			Self::deposit_event(Event::UsageThresholdSet { threshold });
+			Ok(())
+		}
+
+		/// Set or clear the rate limit of an account authorization, so that at most `limit.bytes`
+		/// bytes of it may be used in each period of `limit.period` blocks. This bounds the damage
+		/// a leaked uploader key can do before the authorization is removed. The limit applies to
+		/// everything that uses the authorization, including delegates and transfers, and is
+		/// removed along with the authorization, or when the expired authorization is replaced.
+		///
+		/// The origin for this call must be the pallet's `Authorizer`. Emits
+		/// [`AuthorizationRateSet`](Event::AuthorizationRateSet) when successful.
+		#[pallet::call_index(25)]
+		#[pallet::weight(T::WeightInfo::set_authorization_rate())]
+		pub fn set_authorization_rate(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			limit: Option<RateLimit<BlockNumberFor<T>>>,
+		) -> DispatchResult {
+			T::Authorizer::ensure_origin(origin)?;
+			ensure!(
+				Authorizations::<T>::contains_key(AuthorizationScope::Account(who.clone())),
+				Error::<T>::AuthorizationNotFound
+			);
+			match limit {
+				Some(limit) => {
+					ensure!(!limit.period.is_zero(), Error::<T>::BadRateLimit);
+					AuthorizationRates::<T>::insert(
+						&who,
+						AuthorizationRate { limit, period_start: Zero::zero(), used: 0 },
+					);
+				},
+				None => AuthorizationRates::<T>::remove(&who),
+			}
+			Self::deposit_event(Event::AuthorizationRateSet { who, limit });
+			Ok(())
+		}
+
+		/// Set the authorizations of many accounts at once, for example to load an initial or
+		/// migrated set of authorizations. Large sets should be split over several calls of at
+		/// most [`MAX_FORCED_AUTHORIZATIONS`] entries.
+		///
+		/// Each account's authorization is replaced by the given one, regardless of any existing
+		/// authorization. An authorization with no transactions or no bytes uses up the account's
+		/// existing authorization, which is then kept until the given expiration, as when it is
+		/// used up by storing data.
+		///
+		/// The origin for this call must be root. Emits
+		/// [`AuthorizationsForceSet`](Event::AuthorizationsForceSet) when successful.
+		#[pallet::call_index(28)]
+		#[pallet::weight(T::WeightInfo::force_set_authorizations(authorizations.len() as u32))]
+		pub fn force_set_authorizations(
+			origin: OriginFor<T>,
+			authorizations: BoundedVec<
+				(T::AccountId, AuthorizationDetails<BlockNumberFor<T>>),
+				ConstU32<MAX_FORCED_AUTHORIZATIONS>,
+			>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			let count = authorizations.len() as u32;
+			for (who, details) in authorizations {
+				let scope = AuthorizationScope::Account(who);
+				let existing = Authorizations::<T>::get(&scope);
+				let existed = existing.is_some();
+				let used_up = details.transactions == 0 || details.bytes == 0;
+				if used_up && !existed {
+					continue
+				}
+				// A used up authorization is not removed, as this may reap the account after its
+				// nonce has been incremented
+				let extent = if used_up {
+					AuthorizationExtent { transactions: 0, bytes: 0 }
+				} else {
+					AuthorizationExtent { transactions: details.transactions, bytes: details.bytes }
+				};
+				Authorizations::<T>::insert(
+					&scope,
+					Authorization { extent, expiration: details.expiration },
+				);
+				if !existed {
+					Self::authorization_added(&scope);
+				}
+				if existing.map_or(false, |authorization| Self::expired(authorization.expiration)) {
+					Self::authorization_replaced(&scope);
+				}
+			}
+			Self::deposit_event(Event::AuthorizationsForceSet { count });
+			Ok(())
+		}
+
+		/// Set or clear a standing quota for `who`, so that its account authorization is refreshed
+		/// to at least `quota.transactions` transactions and `quota.bytes` bytes in each period of
+		/// `quota.period` blocks, for example to let a system chain store up to 10 MiB per day.
+		/// The refreshed authorization expires at the end of the period, unless it already
+		/// expires later.
+		///
+		/// The refresh is applied when the authorization is first used in a period, so standing
+		/// quotas cost nothing in periods in which they are not used. Unused quota does not
+		/// carry over to later periods. Unlike rate limits, standing quotas are kept when the
+		/// authorization is used up or expires.
+		///
+		/// The origin for this call must be the pallet's `Authorizer`. Emits
+		/// [`StandingAuthorizationSet`](Event::StandingAuthorizationSet) when successful.
+		#[pallet::call_index(29)]
+		#[pallet::weight(T::WeightInfo::set_standing_authorization())]
+		pub fn set_standing_authorization(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			quota: Option<StandingQuota<BlockNumberFor<T>>>,
+		) -> DispatchResult {
+			T::Authorizer::ensure_origin(origin)?;
+			match quota {
+				Some(quota) => {
+					ensure!(
+						quota.transactions != 0 && quota.bytes != 0 && !quota.period.is_zero(),
+						Error::<T>::BadStandingQuota
+					);
+					StandingAuthorizations::<T>::insert(
+						&who,
+						StandingAuthorization { quota, refreshed: None },
+					);
+				},
+				None => StandingAuthorizations::<T>::remove(&who),
+			}
+			Self::deposit_event(Event::StandingAuthorizationSet { who, quota });
+			Ok(())
+		}
+
+		/// Commit to publishing the data with the given hash, without revealing it yet. The
+		/// signer must have account authorization, of which one transaction and 32 bytes are
+		/// used. The data must not already be being kept. Commitments by other accounts to the
+		/// same data do not prevent committing to it.
+		///
+		/// The data can then be stored with [`reveal`](Self::reveal) within `RevealPeriod`
+		/// blocks, and its canonical publication block (see
+		/// [`commit_block`](Pallet::commit_block)) will be the block of the earliest revealed
+		/// commitment. This proves the data existed before it became public, for example for
+		/// security advisories. Commitments that are not revealed in time are removed in blocks
+		/// with spare weight.
+		///
+		/// Emits [`Committed`](Event::Committed) when successful.
+		#[pallet::call_index(26)]
+		#[pallet::weight(T::WeightInfo::commit())]
+		pub fn commit(origin: OriginFor<T>, content_hash: ContentHash) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+			ensure!(Self::commitment_allowed(&owner, content_hash), Error::<T>::AlreadyCommitted);
+
+			let block = frame_system::Pallet::<T>::block_number();
+			// An expired commitment already holds a provider reference
+			if Commitments::<T>::take(&owner, content_hash).is_none() {
+				frame_system::Pallet::<T>::inc_providers(&owner);
+			}
+			Commitments::<T>::insert(&owner, content_hash, block);
+			Self::deposit_event(Event::Committed { owner, content_hash });
+			Ok(())
+		}
+
+		/// Index and store data off chain that the signer committed to with
+		/// [`commit`](Self::commit) in the last `RevealPeriod` blocks. As [`store`](Self::store),
+		/// but the data's canonical publication block is that of the commitment.
+		///
+		/// Emits [`Stored`](Event::Stored) and [`Revealed`](Event::Revealed) when successful.
+		///
+		/// ## Complexity
+		///
+		/// O(n*log(n)) of data size, as all data is pushed to an in-memory trie.
+		#[pallet::call_index(27)]
+		#[pallet::weight(T::WeightInfo::reveal(data.len() as u32))]
+		pub fn reveal(origin: OriginFor<T>, data: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			// This should have been checked by pre_dispatch_signed
+			ensure!(Self::data_size_ok(data.len()), Error::<T>::BadDataSize);
+			let content_hash = sp_io::hashing::blake2_256(&data);
+			let committed = Self::revealable_commitment(&who, content_hash)
+				.ok_or(Error::<T>::CommitmentNotFound)?;
+
+			let (index, _) = Self::do_store(&data, None, DispatchClass::Normal)?;
+			Self::note_uploader(index, Some(who.clone()));
+			Commitments::<T>::remove(&who, content_hash);
+			Self::release_account(&who);
+			// Another account may have revealed an earlier commitment to the same data
+			CommitBlocks::<T>::mutate(content_hash, |block| {
+				*block = Some(block.map_or(committed, |block| block.min(committed)))
+			});
+			Self::deposit_event(Event::Stored { index, cid: cid(&content_hash), namespace: None });
+			Self::deposit_event(Event::Revealed { index, committed });
+			Ok(())
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Stored data under specified index, optionally in a namespace. The data can be fetched
+		/// over IPFS using `cid`.
+		Stored { index: u32, cid: Cid, namespace: Option<Namespace> },
+		/// Renewed data under specified index.
+		Renewed { index: u32 },
+		/// Storage proof was successfully checked.
+		ProofChecked,
+		/// Data stored under the specified index in `block` dropped out of the retention window.
+		/// It no longer needs to be kept under this index and can no longer be renewed.
+		/// `namespace` and `uploader` are the namespace and signed account, if any, of the
+		/// `store`/`renew`.
+		Expired {
+			block: BlockNumberFor<T>,
+			index: u32,
+			content_hash: ContentHash,
+			namespace: Option<Namespace>,
+			uploader: Option<T::AccountId>,
+		},
+		/// An account `who` was authorized to store `bytes` bytes in `transactions` transactions.
+		AccountAuthorized { who: T::AccountId, transactions: u32, bytes: u64 },
+		/// Authorization was given for a preimage of `hash` (not exceeding `max_size`) to be
+		/// stored by anyone.
+		PreimageAuthorized { hash: ContentHash, max_size: u64 },
+		/// An expired account authorization was removed.
+		ExpiredAccountAuthorizationRemoved { who: T::AccountId },
+		/// An expired preimage authorization was removed.
+		ExpiredPreimageAuthorizationRemoved { hash: ContentHash },
+		/// A chunked upload was started by `who`.
+		UploadStarted {
+			session: UploadSessionId,
+			who: T::AccountId,
+			root: ContentHash,
+			size: u64,
+			chunk_count: u32,
+		},
+		/// Chunk `chunk` of an upload was stored under the specified index.
+		UploadChunkStored { session: UploadSessionId, chunk: u32, index: u32 },
+		/// A chunked upload was completed and verified against its root.
+		UploadFinalized { session: UploadSessionId, receipt: UploadReceipt<BlockNumberFor<T>> },
+		/// The configuration of `namespace` was set, or removed if `config` is `None`.
+		NamespaceConfigSet { namespace: Namespace, config: Option<NamespaceConfigFor<T>> },
+		/// Data stored under the specified index in `block` was retracted.
+		Retracted { block: BlockNumberFor<T>, index: u32, content_hash: ContentHash },
+		/// A chunked upload was resumed and now times out at `expires_at`.
+		UploadResumed { session: UploadSessionId, expires_at: BlockNumberFor<T> },
+		/// A chunked upload by `who` was abandoned and removed without being completed.
+		UploadAbandoned { session: UploadSessionId, who: T::AccountId },
+		/// `transactions` transactions and `bytes` bytes of the authorization of `from` were
+		/// transferred to `to`.
+		AuthorizationTransferred {
+			from: T::AccountId,
+			to: T::AccountId,
+			transactions: u32,
+			bytes: u64,
+		},
+		/// `owner` permitted `delegate` to store up to `bytes_limit` bytes using its account
+		/// authorization until block `expiration`.
+		Delegated {
+			owner: T::AccountId,
+			delegate: T::AccountId,
+			bytes_limit: u64,
+			expiration: BlockNumberFor<T>,
+		},
+		/// The delegation from `owner` to `delegate` was revoked.
+		DelegationRevoked { owner: T::AccountId, delegate: T::AccountId },
+		/// Data stored with the given index was attested to.
+		Attested { index: u32, attestation: Attestation },
+		/// Data stored with the given index is encoded, and `decoded_size` bytes long once
+		/// decoded.
+		Encoded { index: u32, encoding: ContentEncoding, decoded_size: u64 },
+		/// `owner` claimed `name`, pointing it to the data with the given hash.
+		NameClaimed { name: Name, owner: T::AccountId, content_hash: ContentHash },
+		/// `name` was repointed from the data with hash `previous` to that with `content_hash`.
+		NamePointed { name: Name, content_hash: ContentHash, previous: ContentHash },
+		/// `name` was released.
+		NameReleased { name: Name },
+		/// The usage threshold was set or cleared.
+		UsageThresholdSet { threshold: Option<u64> },
+		/// The total size of unexpired data rose above (`exceeded`) or fell back to the usage
+		/// threshold.
+		UsageThresholdCrossed { total_bytes: u64, exceeded: bool },
+		/// The rate limit of the account authorization of `who` was set, or removed if `limit` is
+		/// `None`.
+		AuthorizationRateSet { who: T::AccountId, limit: Option<RateLimit<BlockNumberFor<T>>> },
+		/// `owner` committed to publishing the data with the given hash.
+		Committed { owner: T::AccountId, content_hash: ContentHash },
+		/// Data committed to in block `committed` was stored under the specified index.
+		Revealed { index: u32, committed: BlockNumberFor<T> },
+		/// A commitment by `owner` to publish the data with the given hash expired without the
+		/// data being revealed.
+		CommitmentExpired { owner: T::AccountId, content_hash: ContentHash },
+		/// The authorizations of `count` accounts were set by root.
+		AuthorizationsForceSet { count: u32 },
+		/// The standing quota of `who` was set, or removed if `quota` is `None`.
+		StandingAuthorizationSet {
+			who: T::AccountId,
+			quota: Option<StandingQuota<BlockNumberFor<T>>>,
+		},
+		/// The delegation from `owner` to `delegate` expired and was removed.
+		DelegationExpired { owner: T::AccountId, delegate: T::AccountId },
+	}
+
+	/// Authorizations, keyed by scope.
+	#[pallet::storage]
+	pub(super) type Authorizations<T: Config> =
+		StorageMap<_, Blake2_128Concat, AuthorizationScopeFor<T>, AuthorizationFor<T>, OptionQuery>;
+
+	/// Rate limits of account authorizations, keyed by account. See
+	/// [`set_authorization_rate`](Pallet::set_authorization_rate).
+	#[pallet::storage]
+	pub(super) type AuthorizationRates<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, AuthorizationRateFor<T>, OptionQuery>;
+
+	/// Standing quotas of account authorizations, keyed by account. See
+	/// [`set_standing_authorization`](Pallet::set_standing_authorization).
+	#[pallet::storage]
+	pub(super) type StandingAuthorizations<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, StandingAuthorizationFor<T>, OptionQuery>;
+
+	/// Collection of transaction metadata by block number.
+	#[pallet::storage]
+	#[pallet::getter(fn transaction_roots)]
+	pub(super) type Transactions<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		BlockNumberFor<T>,
+		BoundedVec<TransactionInfo, T::MaxBlockTransactions>,
+		OptionQuery,
+	>;
+
+	/// Count indexed chunks for each block.
+	#[pallet::storage]
+	pub(super) type ChunkCount<T: Config> =
 		StorageMap<_, Blake2_128Concat, BlockNumberFor<T>, u32, ValueQuery>;
 
 	// Intermediates
@@ -572,157 +2049,1182 @@ pub mod pallet {
 	pub(super) type BlockTransactions<T: Config> =
 		StorageValue<_, BoundedVec<TransactionInfo, T::MaxBlockTransactions>, ValueQuery>;
 
-	/// Was the proof checked in this block?
-	#[pallet::storage]
-	pub(super) type ProofChecked<T: Config> = StorageValue<_, bool, ValueQuery>;
+	/// Was the proof checked in this block?
+	#[pallet::storage]
+	pub(super) type ProofChecked<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// Total size in bytes of the data stored or renewed in the current block.
+	#[pallet::storage]
+	pub(super) type BlockBytes<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+	/// Content index entries for data that has not yet expired, keyed by content hash.
+	#[pallet::storage]
+	pub(super) type ContentIndex<T: Config> =
+		StorageMap<_, Blake2_128Concat, ContentHash, ContentEntryFor<T>, OptionQuery>;
+
+	/// Each unexpired and unretracted `store`/`renew` of the data with a given content hash,
+	/// linked to its neighbours, so that the content index can be pointed at the most recent
+	/// remaining copy when a copy is retracted. The most recent copy is the one in the content
+	/// index entry.
+	#[pallet::storage]
+	pub(super) type ContentCopies<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		ContentHash,
+		Twox64Concat,
+		CopyLocation<BlockNumberFor<T>>,
+		CopyLinksFor<T>,
+		OptionQuery,
+	>;
+
+	/// Configuration of each namespace data can be stored in.
+	#[pallet::storage]
+	pub(super) type NamespaceConfigs<T: Config> =
+		StorageMap<_, Blake2_128Concat, Namespace, NamespaceConfigFor<T>, OptionQuery>;
+
+	/// Signed account that submitted each `store`/`renew` transaction that has not yet expired,
+	/// keyed by block number and index within the block.
+	#[pallet::storage]
+	pub(super) type Uploaders<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		Twox64Concat,
+		u32,
+		T::AccountId,
+		OptionQuery,
+	>;
+
+	/// Namespace of each `store`/`renew` transaction in a namespace that has not yet expired or
+	/// been retracted, keyed by block number and index within the block.
+	#[pallet::storage]
+	pub(super) type TransactionNamespaces<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		Twox64Concat,
+		u32,
+		Namespace,
+		OptionQuery,
+	>;
+
+	/// Attestation of each `store_with_attestation` transaction that has not yet expired, keyed by
+	/// block number and index within the block.
+	#[pallet::storage]
+	pub(super) type Attestations<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		Twox64Concat,
+		u32,
+		Attestation,
+		OptionQuery,
+	>;
+
+	/// Total size in bytes of the unexpired and unretracted `store`/`renew` transactions in each
+	/// namespace.
+	#[pallet::storage]
+	pub(super) type NamespaceBytes<T: Config> =
+		StorageMap<_, Blake2_128Concat, Namespace, u64, ValueQuery>;
+
+	/// `store`/`renew` transactions that have been retracted but not yet expired, keyed by block
+	/// number and index within the block.
+	#[pallet::storage]
+	pub(super) type Retracted<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, BlockNumberFor<T>, Twox64Concat, u32, (), OptionQuery>;
+
+	/// Block number at which data expired, keyed by content hash. Entries are removed in
+	/// `on_idle` once they are older than `StoragePeriod` blocks.
+	#[pallet::storage]
+	pub(super) type ExpiredContent<T: Config> =
+		StorageMap<_, Blake2_128Concat, ContentHash, BlockNumberFor<T>, OptionQuery>;
+
+	/// Raw storage key of the [`ExpiredContent`] entry after which the next `on_idle` sweep for
+	/// stale entries continues. `None` if the next sweep should start from the beginning.
+	#[pallet::storage]
+	pub(super) type ExpiredContentSweepCursor<T: Config> =
+		StorageValue<_, BoundedVec<u8, ConstU32<256>>, OptionQuery>;
+
+	/// Raw storage key of the [`Authorizations`] entry after which the next `on_idle` sweep for
+	/// expired authorizations continues. `None` if the next sweep should start from the
+	/// beginning.
+	#[pallet::storage]
+	pub(super) type AuthorizationSweepCursor<T: Config> =
+		StorageValue<_, BoundedVec<u8, ConstU32<256>>, OptionQuery>;
+
+	/// Raw storage key of the [`UploadSessions`] entry after which the next `on_idle` sweep for
+	/// abandoned uploads continues. `None` if the next sweep should start from the beginning.
+	#[pallet::storage]
+	pub(super) type UploadSweepCursor<T: Config> =
+		StorageValue<_, BoundedVec<u8, ConstU32<256>>, OptionQuery>;
+
+	/// Chunked uploads in progress, keyed by session ID.
+	#[pallet::storage]
+	pub(super) type UploadSessions<T: Config> =
+		StorageMap<_, Blake2_128Concat, UploadSessionId, UploadSessionFor<T>, OptionQuery>;
+
+	/// ID to assign to the next chunked upload.
+	#[pallet::storage]
+	pub(super) type NextUploadSessionId<T: Config> = StorageValue<_, UploadSessionId, ValueQuery>;
+
+	/// Completed chunked uploads, keyed by root. Entries are removed in `on_idle` once the data
+	/// of the last chunk has expired.
+	#[pallet::storage]
+	pub(super) type UploadReceipts<T: Config> =
+		StorageMap<_, Blake2_128Concat, ContentHash, CompletedUploadFor<T>, OptionQuery>;
+
+	/// Raw key of the last [`UploadReceipts`] entry visited by the `on_idle` sweep of expired
+	/// receipts. `None` if the next sweep should start from the beginning.
+	#[pallet::storage]
+	pub(super) type ReceiptSweepCursor<T: Config> =
+		StorageValue<_, BoundedVec<u8, ConstU32<256>>, OptionQuery>;
+
+	/// Total size in bytes of the unexpired and unretracted `store`/`renew` transactions.
+	#[pallet::storage]
+	pub(super) type TotalBytes<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+	/// Size in bytes of the unretracted `store`/`renew` transactions expiring in each
+	/// `UsagePeriod`, keyed by the first block of the period.
+	#[pallet::storage]
+	pub(super) type ExpiringBytes<T: Config> =
+		StorageMap<_, Twox64Concat, BlockNumberFor<T>, u64, OptionQuery>;
+
+	/// [`TotalBytes`] at which [`UsageThresholdCrossed`](Event::UsageThresholdCrossed) is
+	/// emitted.
+	#[pallet::storage]
+	pub(super) type UsageThreshold<T: Config> = StorageValue<_, u64, OptionQuery>;
+
+	/// Blocks of pending commitments to publish data, keyed by the committing account and
+	/// content hash. See [`commit`](Pallet::commit).
+	#[pallet::storage]
+	pub(super) type Commitments<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		ContentHash,
+		BlockNumberFor<T>,
+		OptionQuery,
+	>;
+
+	/// Canonical publication block of data stored with [`reveal`](Pallet::reveal), keyed by
+	/// content hash. Entries are removed when the data is no longer kept.
+	#[pallet::storage]
+	pub(super) type CommitBlocks<T: Config> =
+		StorageMap<_, Blake2_128Concat, ContentHash, BlockNumberFor<T>, OptionQuery>;
+
+	/// Raw key of the last [`Commitments`] entry visited by the `on_idle` sweep of expired
+	/// commitments. `None` if the next sweep should start from the beginning.
+	#[pallet::storage]
+	pub(super) type CommitmentSweepCursor<T: Config> =
+		StorageValue<_, BoundedVec<u8, ConstU32<256>>, OptionQuery>;
+
+	/// The data each claimed name points to. See [`claim_name`](Pallet::claim_name).
+	#[pallet::storage]
+	pub(super) type Names<T: Config> =
+		StorageMap<_, Blake2_128Concat, Name, NamePointerFor<T>, OptionQuery>;
+
+	/// Delegations, keyed by delegate. See [`delegate`](Pallet::delegate).
+	#[pallet::storage]
+	pub(super) type Delegations<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, DelegationFor<T>, OptionQuery>;
+
+	/// Raw key of the last [`Delegations`] entry visited by the `on_idle` sweep of expired
+	/// delegations. `None` if the next sweep should start from the beginning.
+	#[pallet::storage]
+	pub(super) type DelegationSweepCursor<T: Config> =
+		StorageValue<_, BoundedVec<u8, ConstU32<256>>, OptionQuery>;
+
+	#[pallet::inherent]
+	impl<T: Config> ProvideInherent for Pallet<T> {
+		type Call = Call<T>;
+		type Error = InherentError;
+		const INHERENT_IDENTIFIER: InherentIdentifier = INHERENT_IDENTIFIER;
+
+		fn create_inherent(data: &InherentData) -> Option<Self::Call> {
+			let proof = data
+				.get_data::<TransactionStorageProof>(&Self::INHERENT_IDENTIFIER)
+				.unwrap_or(None);
+			proof.map(|proof| Call::check_proof { proof })
+		}
+
+		fn check_inherent(
+			_call: &Self::Call,
+			_data: &InherentData,
+		) -> result::Result<(), Self::Error> {
+			Ok(())
+		}
+
+		fn is_inherent(call: &Self::Call) -> bool {
+			matches!(call, Call::check_proof { .. })
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			Self::check_unsigned(call, CheckContext::Validate)?.ok_or(IMPOSSIBLE.into())
+		}
+
+		fn pre_dispatch(call: &Self::Call) -> Result<(), TransactionValidityError> {
+			Self::check_unsigned(call, CheckContext::PreDispatch).map(|_| ())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Returns `true` if the system is beyond the given expiration point.
+		fn expired(expiration: BlockNumberFor<T>) -> bool {
+			let now = frame_system::Pallet::<T>::block_number();
+			now >= expiration
+		}
+
+		/// Authorize data storage.
+		fn authorize(scope: AuthorizationScopeFor<T>, transactions: u32, bytes: u64) {
+			let expiration = frame_system::Pallet::<T>::block_number()
+				.saturating_add(T::AuthorizationPeriod::get());
+
+			let (existed, replaced) = Authorizations::<T>::mutate(&scope, |maybe_authorization| {
+				let existed = maybe_authorization.is_some();
+
+				if let Some(authorization) = maybe_authorization {
+					if Self::expired(authorization.expiration) {
+						*maybe_authorization = None;
+					}
+				}
+				let replaced = existed && maybe_authorization.is_none();
+
+				if let Some(authorization) = maybe_authorization {
+					// An unexpired authorization already exists. Extend it.
+					match scope {
+						AuthorizationScope::Account(_) => {
+							// Add
+							authorization.extent.transactions =
+								authorization.extent.transactions.saturating_add(transactions);
+							authorization.extent.bytes =
+								authorization.extent.bytes.saturating_add(bytes);
+						},
+						AuthorizationScope::Preimage(_) => {
+							// Max
+							authorization.extent.transactions =
+								authorization.extent.transactions.max(transactions);
+							authorization.extent.bytes = authorization.extent.bytes.max(bytes);
+						},
+					}
+					authorization.expiration = expiration;
+				} else {
+					// No previous authorization, or it expired. Create a fresh one.
+					*maybe_authorization = Some(Authorization {
+						extent: AuthorizationExtent { transactions, bytes },
+						expiration,
+					});
+				}
+
+				(existed, replaced)
+			});
+
+			// An expired authorization is replaced, keeping its account reference
+			if !existed {
+				Self::authorization_added(&scope);
+			}
+			if replaced {
+				Self::authorization_replaced(&scope);
+			}
+		}
+
+		/// Credit `who` with an account authorization to store `bytes` bytes in `transactions`
+		/// transactions. If `who` has no unexpired authorization, the new one expires at
+		/// `expiration`.
+		fn credit_account(
+			who: T::AccountId,
+			transactions: u32,
+			bytes: u64,
+			expiration: BlockNumberFor<T>,
+		) {
+			let scope = AuthorizationScope::Account(who);
+			let (existed, replaced) = Authorizations::<T>::mutate(&scope, |maybe_authorization| {
+				let existed = maybe_authorization.is_some();
+				match maybe_authorization {
+					Some(authorization) if !Self::expired(authorization.expiration) => {
+						authorization.extent.transactions.saturating_accrue(transactions);
+						authorization.extent.bytes.saturating_accrue(bytes);
+						(existed, false)
+					},
+					_ => {
+						*maybe_authorization = Some(Authorization {
+							extent: AuthorizationExtent { transactions, bytes },
+							expiration,
+						});
+						(existed, existed)
+					},
+				}
+			});
+			if !existed {
+				Self::authorization_added(&scope);
+			}
+			if replaced {
+				Self::authorization_replaced(&scope);
+			}
+		}
+
+		/// Returns the block in which data stored or renewed in `block` expires.
+		fn expiry(block: BlockNumberFor<T>) -> BlockNumberFor<T> {
+			block.saturating_add(T::StoragePeriod::get()).saturating_add(One::one())
+		}
+
+		/// Returns the first block of the `UsagePeriod` containing `block`.
+		fn usage_period(block: BlockNumberFor<T>) -> BlockNumberFor<T> {
+			block - block % T::UsagePeriod::get()
+		}
+
+		/// Account for `bytes` more bytes being kept until `expiry`.
+		fn note_usage(expiry: BlockNumberFor<T>, bytes: u64) {
+			ExpiringBytes::<T>::mutate(Self::usage_period(expiry), |expiring| {
+				*expiring = Some(expiring.unwrap_or(0).saturating_add(bytes))
+			});
+			Self::set_total_bytes(TotalBytes::<T>::get().saturating_add(bytes));
+		}
+
+		/// Account for `bytes` bytes that were to be kept until `expiry` no longer being kept.
+		fn release_usage(expiry: BlockNumberFor<T>, bytes: u64) {
+			ExpiringBytes::<T>::mutate_exists(Self::usage_period(expiry), |expiring| {
+				let remaining = expiring.unwrap_or(0).saturating_sub(bytes);
+				*expiring = (remaining != 0).then_some(remaining);
+			});
+			Self::set_total_bytes(TotalBytes::<T>::get().saturating_sub(bytes));
+		}
+
+		/// Update [`TotalBytes`], emitting
+		/// [`UsageThresholdCrossed`](Event::UsageThresholdCrossed) if it crosses the threshold.
+		fn set_total_bytes(total_bytes: u64) {
+			let previous = TotalBytes::<T>::get();
+			TotalBytes::<T>::put(total_bytes);
+			if let Some(threshold) = UsageThreshold::<T>::get() {
+				let exceeded = total_bytes > threshold;
+				if exceeded != (previous > threshold) {
+					Self::deposit_event(Event::UsageThresholdCrossed { total_bytes, exceeded });
+				}
+			}
+		}
+
+		/// Called when an authorization is added to storage. Account authorizations provide a
+		/// reference to the account.
+		fn authorization_added(scope: &AuthorizationScopeFor<T>) {
+			if let AuthorizationScope::Account(who) = scope {
+				frame_system::Pallet::<T>::inc_providers(who);
+			}
+		}
+
+		/// Called when an expired authorization is replaced in storage by a new one. The rate limit
+		/// of an account authorization is removed, as for an authorization removed on expiry, so
+		/// that it doesn't carry over to the new one.
+		fn authorization_replaced(scope: &AuthorizationScopeFor<T>) {
+			if let AuthorizationScope::Account(who) = scope {
+				AuthorizationRates::<T>::remove(who);
+			}
+		}
+
+		/// Called when an authorization is removed from storage. The account of an account
+		/// authorization is reaped if nothing else references it.
+		fn authorization_removed(scope: &AuthorizationScopeFor<T>) {
+			if let AuthorizationScope::Account(who) = scope {
+				AuthorizationRates::<T>::remove(who);
+				Self::release_account(who);
+			}
+		}
+
+		/// Drop a provider reference to `who`, reaping the account if nothing else references
+		/// it.
+		fn release_account(who: &T::AccountId) {
+			if let Err(err) = frame_system::Pallet::<T>::dec_providers(who) {
+				log::warn!(
+					target: LOG_TARGET,
+					"Failed to decrement provider reference count for account {:?}, \
+					leaking reference: {:?}",
+					who, err
+				);
+			}
+		}
+
+		/// Returns the scope of the account authorization to be used by `who` to store `bytes`
+		/// bytes in `transactions` transactions, and whether it is used through a delegation.
+		/// `who`'s own authorization is used if sufficient; otherwise that of the account which
+		/// delegated to `who`, if the delegation allows it.
+		fn account_scope(
+			who: &T::AccountId,
+			transactions: u32,
+			bytes: u64,
+		) -> (AuthorizationScopeFor<T>, bool) {
+			let own = AuthorizationScope::Account(who.clone());
+			if Self::check_authorization(own, transactions, bytes, false).is_ok() {
+				return (AuthorizationScope::Account(who.clone()), false)
+			}
+			match Delegations::<T>::get(who).filter(|delegation| {
+				!Self::expired(delegation.expiration) &&
+					delegation.bytes_used.saturating_add(bytes) <= delegation.bytes_limit
+			}) {
+				Some(delegation) => (AuthorizationScope::Account(delegation.owner), true),
+				None => (AuthorizationScope::Account(who.clone()), false),
+			}
+		}
+
+		/// Remove an expired authorization.
+		fn remove_expired_authorization(scope: AuthorizationScopeFor<T>) -> DispatchResult {
+			// In the case of a regular unsigned transaction, pre_dispatch should have checked that
+			// the authorization exists and has expired
+			let Some(authorization) = Authorizations::<T>::take(&scope) else {
+				return Err(Error::<T>::AuthorizationNotFound.into())
+			};
+			ensure!(Self::expired(authorization.expiration), Error::<T>::AuthorizationNotExpired);
+			Self::authorization_removed(&scope);
+			Ok(())
+		}
+
+		/// Remove expired authorizations, continuing from where the previous call stopped, until
+		/// `limit` is used up. Returns the weight consumed.
+		fn remove_expired_authorizations(limit: Weight) -> Weight {
+			let db_weight = T::DbWeight::get();
+			let mut meter = WeightMeter::from_limit(limit);
+
+			// Cursor read and write
+			if !meter.check_accrue(db_weight.reads_writes(1, 1)) {
+				return meter.consumed
+			}
+
+			let mut authorizations = match AuthorizationSweepCursor::<T>::get() {
+				Some(cursor) => Authorizations::<T>::iter_from(cursor.into_inner()),
+				None => Authorizations::<T>::iter(),
+			};
+			loop {
+				if !meter.check_accrue(db_weight.reads_writes(1, 1)) {
+					// Out of weight; continue from here next time
+					let cursor = authorizations.last_raw_key().to_vec().try_into().ok();
+					AuthorizationSweepCursor::<T>::set(cursor);
+					break
+				}
+				let Some((scope, authorization)) = authorizations.next() else {
+					// Swept everything; start from the beginning next time
+					AuthorizationSweepCursor::<T>::kill();
+					break
+				};
+				if !Self::expired(authorization.expiration) {
+					continue
+				}
+				Authorizations::<T>::remove(&scope);
+				Self::authorization_removed(&scope);
+				Self::deposit_event(match scope {
+					AuthorizationScope::Account(who) =>
+						Event::ExpiredAccountAuthorizationRemoved { who },
+					AuthorizationScope::Preimage(hash) =>
+						Event::ExpiredPreimageAuthorizationRemoved { hash },
+				});
+			}
+
+			meter.consumed
+		}
+
+		/// Returns `true` if data that expired at block `expired_at` should still be reported as
+		/// expired, rather than unknown.
+		fn expiry_recent(expired_at: BlockNumberFor<T>) -> bool {
+			let now = frame_system::Pallet::<T>::block_number();
+			now < expired_at.saturating_add(T::StoragePeriod::get())
+		}
+
+		/// Remove [`ExpiredContent`] entries older than `StoragePeriod` blocks, continuing from
+		/// where the previous call stopped, until `limit` is used up. Returns the weight consumed.
+		fn remove_stale_expired_content(limit: Weight) -> Weight {
+			let db_weight = T::DbWeight::get();
+			let mut meter = WeightMeter::from_limit(limit);
 
-	#[pallet::inherent]
-	impl<T: Config> ProvideInherent for Pallet<T> {
-		type Call = Call<T>;
-		type Error = InherentError;
-		const INHERENT_IDENTIFIER: InherentIdentifier = INHERENT_IDENTIFIER;
+			// Cursor read and write
+			if !meter.check_accrue(db_weight.reads_writes(1, 1)) {
+				return meter.consumed
+			}
 
-		fn create_inherent(data: &InherentData) -> Option<Self::Call> {
-			let proof = data
-				.get_data::<TransactionStorageProof>(&Self::INHERENT_IDENTIFIER)
-				.unwrap_or(None);
-			proof.map(|proof| Call::check_proof { proof })
+			let mut expired = match ExpiredContentSweepCursor::<T>::get() {
+				Some(cursor) => ExpiredContent::<T>::iter_from(cursor.into_inner()),
+				None => ExpiredContent::<T>::iter(),
+			};
+			loop {
+				if !meter.check_accrue(db_weight.reads_writes(1, 1)) {
+					// Out of weight; continue from here next time
+					let cursor = expired.last_raw_key().to_vec().try_into().ok();
+					ExpiredContentSweepCursor::<T>::set(cursor);
+					break
+				}
+				let Some((content_hash, expired_at)) = expired.next() else {
+					// Swept everything; start from the beginning next time
+					ExpiredContentSweepCursor::<T>::kill();
+					break
+				};
+				if !Self::expiry_recent(expired_at) {
+					ExpiredContent::<T>::remove(content_hash);
+				}
+			}
+
+			meter.consumed
 		}
 
-		fn check_inherent(
-			_call: &Self::Call,
-			_data: &InherentData,
-		) -> result::Result<(), Self::Error> {
-			Ok(())
+		/// Returns the block at which an upload supplied with a chunk or resumed now times out.
+		fn upload_expiry() -> BlockNumberFor<T> {
+			frame_system::Pallet::<T>::block_number().saturating_add(T::UploadTimeout::get())
 		}
 
-		fn is_inherent(call: &Self::Call) -> bool {
-			matches!(call, Call::check_proof { .. })
+		/// Remove uploads that timed out more than `UploadTimeout` blocks ago, continuing from
+		/// where the previous call stopped, until `limit` is used up. Returns the weight consumed.
+		///
+		/// The transaction used to start each removed upload is refunded to the owner's account
+		/// authorization, if it has not expired. Authorization for the chunks is only consumed as
+		/// they are supplied, so there is nothing else to refund.
+		fn remove_abandoned_uploads(limit: Weight) -> Weight {
+			let db_weight = T::DbWeight::get();
+			let mut meter = WeightMeter::from_limit(limit);
+
+			// Cursor read and write
+			if !meter.check_accrue(db_weight.reads_writes(1, 1)) {
+				return meter.consumed
+			}
+
+			let mut uploads = match UploadSweepCursor::<T>::get() {
+				Some(cursor) => UploadSessions::<T>::iter_from(cursor.into_inner()),
+				None => UploadSessions::<T>::iter(),
+			};
+			loop {
+				// Upload read and removal, and the owner's authorization read and refund
+				if !meter.check_accrue(db_weight.reads_writes(2, 2)) {
+					// Out of weight; continue from here next time
+					let cursor = uploads.last_raw_key().to_vec().try_into().ok();
+					UploadSweepCursor::<T>::set(cursor);
+					break
+				}
+				let Some((session, upload)) = uploads.next() else {
+					// Swept everything; start from the beginning next time
+					UploadSweepCursor::<T>::kill();
+					break
+				};
+				if !Self::expired(upload.expires_at.saturating_add(T::UploadTimeout::get())) {
+					continue
+				}
+				UploadSessions::<T>::remove(session);
+				let scope = AuthorizationScope::Account(upload.owner.clone());
+				Authorizations::<T>::mutate(scope, |maybe_authorization| {
+					if let Some(authorization) = maybe_authorization {
+						if !Self::expired(authorization.expiration) {
+							authorization.extent.transactions.saturating_inc();
+						}
+					}
+				});
+				Self::deposit_event(Event::UploadAbandoned { session, who: upload.owner });
+			}
+
+			meter.consumed
 		}
-	}
 
-	#[pallet::validate_unsigned]
-	impl<T: Config> ValidateUnsigned for Pallet<T> {
-		type Call = Call<T>;
+		/// Remove upload receipts whose last chunk has expired, continuing from where the previous
+		/// call stopped, until `limit` is used up. Returns the weight consumed.
+		fn remove_expired_upload_receipts(limit: Weight) -> Weight {
+			let db_weight = T::DbWeight::get();
+			let mut meter = WeightMeter::from_limit(limit);
 
-		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
-			Self::check_unsigned(call, CheckContext::Validate)?.ok_or(IMPOSSIBLE.into())
+			// Cursor read and write
+			if !meter.check_accrue(db_weight.reads_writes(1, 1)) {
+				return meter.consumed
+			}
+
+			let mut receipts = match ReceiptSweepCursor::<T>::get() {
+				Some(cursor) => UploadReceipts::<T>::iter_from(cursor.into_inner()),
+				None => UploadReceipts::<T>::iter(),
+			};
+			loop {
+				if !meter.check_accrue(db_weight.reads_writes(1, 1)) {
+					// Out of weight; continue from here next time
+					let cursor = receipts.last_raw_key().to_vec().try_into().ok();
+					ReceiptSweepCursor::<T>::set(cursor);
+					break
+				}
+				let Some((root, upload)) = receipts.next() else {
+					// Swept everything; start from the beginning next time
+					ReceiptSweepCursor::<T>::kill();
+					break
+				};
+				if Self::expired(Self::expiry(upload.receipt.last_block)) {
+					UploadReceipts::<T>::remove(root);
+				}
+			}
+
+			meter.consumed
 		}
 
-		fn pre_dispatch(call: &Self::Call) -> Result<(), TransactionValidityError> {
-			Self::check_unsigned(call, CheckContext::PreDispatch).map(|_| ())
+		/// Returns `true` if `who` may commit to the data with the given hash: it is not being
+		/// kept, and any previous commitment to it by `who` has expired.
+		fn commitment_allowed(who: &T::AccountId, content_hash: ContentHash) -> bool {
+			!ContentIndex::<T>::contains_key(content_hash) &&
+				Commitments::<T>::get(who, content_hash).map_or(true, Self::reveal_expired)
 		}
-	}
 
-	impl<T: Config> Pallet<T> {
-		/// Returns `true` if the system is beyond the given expiration point.
-		fn expired(expiration: BlockNumberFor<T>) -> bool {
+		/// Returns `true` if data committed to in the given block can no longer be revealed.
+		fn reveal_expired(committed: BlockNumberFor<T>) -> bool {
 			let now = frame_system::Pallet::<T>::block_number();
-			now >= expiration
+			now > committed.saturating_add(T::RevealPeriod::get())
 		}
 
-		/// Authorize data storage.
-		fn authorize(scope: AuthorizationScopeFor<T>, transactions: u32, bytes: u64) {
-			let expiration = frame_system::Pallet::<T>::block_number()
-				.saturating_add(T::AuthorizationPeriod::get());
+		/// Returns the block of the commitment by `who` to the data with the given hash, if it can
+		/// still be revealed.
+		fn revealable_commitment(
+			who: &T::AccountId,
+			content_hash: ContentHash,
+		) -> Option<BlockNumberFor<T>> {
+			Commitments::<T>::get(who, content_hash)
+				.filter(|committed| !Self::reveal_expired(*committed))
+		}
 
-			Authorizations::<T>::mutate(&scope, |maybe_authorization| {
-				if let Some(authorization) = maybe_authorization {
-					if Self::expired(authorization.expiration) {
-						*maybe_authorization = None;
-					}
+		/// Remove commitments that can no longer be revealed, continuing from where the previous
+		/// call stopped, until `limit` is used up. Returns the weight consumed.
+		fn remove_expired_commitments(limit: Weight) -> Weight {
+			let db_weight = T::DbWeight::get();
+			let mut meter = WeightMeter::from_limit(limit);
+
+			// Cursor read and write
+			if !meter.check_accrue(db_weight.reads_writes(1, 1)) {
+				return meter.consumed
+			}
+
+			let mut commitments = match CommitmentSweepCursor::<T>::get() {
+				Some(cursor) => Commitments::<T>::iter_from(cursor.into_inner()),
+				None => Commitments::<T>::iter(),
+			};
+			loop {
+				// Commitment removal and account release
+				if !meter.check_accrue(db_weight.reads_writes(2, 2)) {
+					// Out of weight; continue from here next time
+					let cursor = commitments.last_raw_key().to_vec().try_into().ok();
+					CommitmentSweepCursor::<T>::set(cursor);
+					break
+				}
+				let Some((owner, content_hash, committed)) = commitments.next() else {
+					// Swept everything; start from the beginning next time
+					CommitmentSweepCursor::<T>::kill();
+					break
+				};
+				if !Self::reveal_expired(committed) {
+					continue
 				}
+				Commitments::<T>::remove(&owner, content_hash);
+				Self::release_account(&owner);
+				Self::deposit_event(Event::CommitmentExpired { owner, content_hash });
+			}
 
-				if let Some(authorization) = maybe_authorization {
-					// An unexpired authorization already exists. Extend it.
-					match scope {
-						AuthorizationScope::Account(_) => {
-							// Add
-							authorization.extent.transactions =
-								authorization.extent.transactions.saturating_add(transactions);
-							authorization.extent.bytes =
-								authorization.extent.bytes.saturating_add(bytes);
-						},
-						AuthorizationScope::Preimage(_) => {
-							// Max
-							authorization.extent.transactions =
-								authorization.extent.transactions.max(transactions);
-							authorization.extent.bytes = authorization.extent.bytes.max(bytes);
-						},
+			meter.consumed
+		}
+
+		/// Remove expired delegations, continuing from where the previous call stopped, until
+		/// `limit` is used up. Returns the weight consumed.
+		fn remove_expired_delegations(limit: Weight) -> Weight {
+			let db_weight = T::DbWeight::get();
+			let mut meter = WeightMeter::from_limit(limit);
+
+			// Cursor read and write
+			if !meter.check_accrue(db_weight.reads_writes(1, 1)) {
+				return meter.consumed
+			}
+
+			let mut delegations = match DelegationSweepCursor::<T>::get() {
+				Some(cursor) => Delegations::<T>::iter_from(cursor.into_inner()),
+				None => Delegations::<T>::iter(),
+			};
+			loop {
+				// Delegation removal and account release
+				if !meter.check_accrue(db_weight.reads_writes(2, 2)) {
+					// Out of weight; continue from here next time
+					let cursor = delegations.last_raw_key().to_vec().try_into().ok();
+					DelegationSweepCursor::<T>::set(cursor);
+					break
+				}
+				let Some((delegate, delegation)) = delegations.next() else {
+					// Swept everything; start from the beginning next time
+					DelegationSweepCursor::<T>::kill();
+					break
+				};
+				if !Self::expired(delegation.expiration) {
+					continue
+				}
+				Delegations::<T>::remove(&delegate);
+				Self::release_account(&delegate);
+				Self::deposit_event(Event::DelegationExpired { owner: delegation.owner, delegate });
+			}
+
+			meter.consumed
+		}
+
+		/// Returns the availability status of the data with the given hash. Pending and completed
+		/// chunked uploads are looked up by their committed root.
+		pub fn data_status(hash: ContentHash) -> DataStatus<BlockNumberFor<T>> {
+			if let Some(entry) = ContentIndex::<T>::get(hash) {
+				return DataStatus::Included(entry.block)
+			}
+			if let Some(upload) = UploadReceipts::<T>::get(hash) {
+				return DataStatus::Uploaded(upload.receipt)
+			}
+			// Only used off chain, so iterating over all uploads is fine
+			if let Some(upload) = UploadSessions::<T>::iter_values().find(|u| u.root == hash) {
+				let received = upload.chunk_hashes.len() as u32;
+				return DataStatus::PendingChunks(upload.chunk_count.saturating_sub(received))
+			}
+			match ExpiredContent::<T>::get(hash) {
+				Some(expired_at) if Self::expiry_recent(expired_at) => DataStatus::Expired,
+				_ => DataStatus::Unknown,
+			}
+		}
+
+		fn authorization_extent(scope: AuthorizationScopeFor<T>) -> AuthorizationExtent {
+			let Some(authorization) = Self::refreshed_authorization(&scope) else {
+				return AuthorizationExtent { transactions: 0, bytes: 0 }
+			};
+			if Self::expired(authorization.expiration) {
+				AuthorizationExtent { transactions: 0, bytes: 0 }
+			} else {
+				authorization.extent
+			}
+		}
+
+		/// Returns the (unused and unexpired) authorization extent for the given account.
+		pub fn account_authorization_extent(who: T::AccountId) -> AuthorizationExtent {
+			Self::authorization_extent(AuthorizationScope::Account(who))
+		}
+
+		/// Returns the storage key of the [`TransactionInfo`]s of the data stored or renewed in
+		/// `block`. A read proof of this key proves what was stored in `block`.
+		pub fn transactions_key(block: BlockNumberFor<T>) -> Vec<u8> {
+			Transactions::<T>::hashed_key_for(block)
+		}
+
+		/// Returns the storage usage, for tuning budgets.
+		pub fn storage_usage() -> StorageUsage<BlockNumberFor<T>> {
+			let mut expiring_bytes: Vec<_> = ExpiringBytes::<T>::iter().collect();
+			expiring_bytes.sort_unstable_by_key(|(period, _)| *period);
+			let namespaces = NamespaceConfigs::<T>::iter()
+				.map(|(namespace, config)| {
+					let bytes = NamespaceBytes::<T>::get(&namespace);
+					(namespace, bytes, config.max_total_bytes)
+				})
+				.collect();
+			StorageUsage { total_bytes: TotalBytes::<T>::get(), expiring_bytes, namespaces }
+		}
+
+		/// Returns the receipt of the completed chunked upload with the given root, or `None` if
+		/// there is no such upload or its last chunk has expired.
+		pub fn upload_receipt(root: ContentHash) -> Option<UploadReceipt<BlockNumberFor<T>>> {
+			UploadReceipts::<T>::get(root).map(|upload| upload.receipt)
+		}
+
+		/// Returns a proof of the hash of chunk `chunk` of the completed chunked upload with the
+		/// given root, or `None` if there is no such chunk or the upload's last chunk has expired.
+		/// Only used off chain; the trie of chunk hashes is rebuilt in memory.
+		pub fn upload_chunk_proof(root: ContentHash, chunk: u32) -> Option<ChunkProof> {
+			use sp_trie::TrieMut;
+
+			let upload = UploadReceipts::<T>::get(root)?;
+			let content_hash = *upload.chunk_hashes.get(chunk as usize)?;
+			let mut db = sp_trie::MemoryDB::<BlakeTwo256>::default();
+			let mut trie_root = Default::default();
+			{
+				let mut trie =
+					sp_trie::TrieDBMutBuilderV1::<BlakeTwo256>::new(&mut db, &mut trie_root)
+						.build();
+				for (index, hash) in upload.chunk_hashes.iter().enumerate() {
+					trie.insert(&encode_index(index as u32), hash).ok()?;
+				}
+			}
+			let proof = sp_trie::generate_trie_proof::<sp_trie::LayoutV1<BlakeTwo256>, _, _, _>(
+				&db,
+				trie_root,
+				&[encode_index(chunk)],
+			)
+			.ok()?;
+			Some(ChunkProof { content_hash, proof })
+		}
+
+		/// Returns the canonical publication block of the data with the given hash: the block of
+		/// the earliest revealed [`commit`](Pallet::commit) if the data was stored with
+		/// [`reveal`](Pallet::reveal), or `None` otherwise or if the data is no longer kept.
+		pub fn commit_block(content_hash: ContentHash) -> Option<BlockNumberFor<T>> {
+			CommitBlocks::<T>::get(content_hash)
+		}
+
+		/// Returns the randomness used to select the chunk proved by the storage proof in the
+		/// current block, or `None` if the parent block hash is used instead. Queried against the
+		/// state of an imported block, this is the randomness for the proof in its child, which
+		/// the child's author uses to build the proof.
+		pub fn proof_randomness() -> Option<T::Hash> {
+			T::Randomness::random(b"transaction-storage-proof").0
+		}
+
+		/// Returns the data that expires within the next `blocks` blocks, as (content hash,
+		/// namespace, expiry block) tuples in order of expiry, so that it can be pinned elsewhere
+		/// before it is dropped. Data that has been renewed or stored again is listed under its
+		/// latest expiry only. At most `limit` entries, capped to [`MAX_EXPIRING_DATA`], are
+		/// returned after skipping the first `skip`.
+		pub fn expiring_within(
+			blocks: BlockNumberFor<T>,
+			skip: u32,
+			limit: u32,
+		) -> Vec<(ContentHash, Option<Namespace>, BlockNumberFor<T>)> {
+			let now = frame_system::Pallet::<T>::block_number();
+			let period = T::StoragePeriod::get();
+			// Data stored or renewed in `block` is dropped in block `expiry(block)`, so the next
+			// block drops the data of block `now - period`
+			let mut block = now.saturating_sub(period);
+			let end = now.saturating_add(blocks).saturating_sub(period);
+			let end = end.min(now.saturating_add(One::one()));
+			let limit = limit.min(MAX_EXPIRING_DATA) as usize;
+			let mut skip = skip;
+			let mut expiring = Vec::new();
+			// Only used off chain, so reading every transaction in the range is fine
+			while block < end && expiring.len() < limit {
+				let transactions = Transactions::<T>::get(block).unwrap_or_default();
+				for (index, info) in transactions.into_iter().enumerate() {
+					let content_hash: ContentHash = info.content_hash.into();
+					let namespace = match ContentIndex::<T>::get(content_hash) {
+						Some(entry) if entry.block == block && entry.index == index as u32 =>
+							entry.namespace,
+						// Retracted data, and data that is kept for longer, has no entry for
+						// this transaction
+						_ => continue,
+					};
+					if skip > 0 {
+						skip -= 1;
+						continue
+					}
+					expiring.push((content_hash, namespace, Self::expiry(block)));
+					if expiring.len() == limit {
+						break
 					}
-					authorization.expiration = expiration;
-				} else {
-					// No previous authorization, or it expired. Create a fresh one.
-					*maybe_authorization = Some(Authorization {
-						extent: AuthorizationExtent { transactions, bytes },
-						expiration,
-					});
 				}
+				block.saturating_inc();
+			}
+			expiring
+		}
+
+		/// Returns the hash of the data `name` points to, or `None` if `name` has not been
+		/// claimed. The data may no longer be kept.
+		pub fn resolve_name(name: &[u8]) -> Option<ContentHash> {
+			let name = Name::try_from(name.to_vec()).ok()?;
+			Names::<T>::get(name).map(|pointer| pointer.content_hash)
+		}
+
+		/// Returns the block number and index within the block of the most recent `store` or
+		/// `renew` of the data with the given hash, or `None` if no such data is being kept.
+		pub fn content_location(hash: ContentHash) -> Option<(BlockNumberFor<T>, u32)> {
+			ContentIndex::<T>::get(hash).map(|entry| (entry.block, entry.index))
+		}
+
+		/// Returns the unused part and expiry of the given account's authorization, or `None` if
+		/// the account has no unexpired authorization.
+		pub fn account_authorization_status(
+			who: T::AccountId,
+		) -> Option<AuthorizationStatus<BlockNumberFor<T>>> {
+			Self::refreshed_authorization(&AuthorizationScope::Account(who))
+				.filter(|authorization| !Self::expired(authorization.expiration))
+				.map(|authorization| AuthorizationStatus {
+					bytes_left: authorization.extent.bytes,
+					transactions_left: authorization.extent.transactions,
+					expires_at: authorization.expiration,
+				})
+		}
+
+		/// Returns the (unused and unexpired) authorization extent for the given content hash.
+		pub fn preimage_authorization_extent(hash: ContentHash) -> AuthorizationExtent {
+			Self::authorization_extent(AuthorizationScope::Preimage(hash))
+		}
+
+		/// Returns the validity of the given call, signed by the given account.
+		///
+		/// This is equivalent to `validate_unsigned` but for signed transactions. It should be
+		/// called from a `SignedExtension` implementation.
+		pub fn validate_signed(who: &T::AccountId, call: &Call<T>) -> TransactionValidity {
+			Self::check_signed(who, call, CheckContext::Validate)?.ok_or(IMPOSSIBLE.into())
+		}
+
+		/// Check the validity of the given call, signed by the given account, and consume
+		/// authorization for it.
+		///
+		/// This is equivalent to `pre_dispatch` but for signed transactions. It should be called
+		/// from a `SignedExtension` implementation.
+		pub fn pre_dispatch_signed(
+			who: &T::AccountId,
+			call: &Call<T>,
+		) -> Result<(), TransactionValidityError> {
+			Self::check_signed(who, call, CheckContext::PreDispatch).map(|_| ())
+		}
+
+		/// Returns `true` if a blob of the given size can be stored.
+		fn data_size_ok(size: usize) -> bool {
+			(size > 0) && (size <= T::MaxTransactionSize::get() as usize)
+		}
+
+		/// Index the given data of the given class and record it in the current block. Returns
+		/// the index of the new transaction in the block and the hash of the data.
+		fn do_store(
+			data: &[u8],
+			namespace: Option<Namespace>,
+			class: DispatchClass,
+		) -> Result<(u32, ContentHash), DispatchError> {
+			// Chunk data and compute storage root
+			let chunks: Vec<_> = data.chunks(CHUNK_SIZE).map(|c| c.to_vec()).collect();
+			let chunk_count = chunks.len();
+			debug_assert_eq!(chunk_count, num_chunks(data.len() as u32) as usize);
+			let root = sp_io::trie::blake2_256_ordered_root(chunks, sp_runtime::StateVersion::V1);
+
+			let extrinsic_index =
+				<frame_system::Pallet<T>>::extrinsic_index().ok_or(Error::<T>::BadContext)?;
+			let content_hash = sp_io::hashing::blake2_256(data);
+			if let Some(namespace) = &namespace {
+				// Should have been checked by the caller
+				ensure!(Self::namespace_has_room(namespace, data.len()), Error::<T>::BadNamespace);
+			}
+			Self::note_block_bytes(data.len() as u32, class)?;
+			sp_io::transaction_index::index(extrinsic_index, data.len() as u32, content_hash);
+
+			let mut index = 0;
+			<BlockTransactions<T>>::mutate(|transactions| {
+				let total_chunks =
+					transactions.last().map_or(0, |t| t.block_chunks) + (chunk_count as u32);
+				index = transactions.len() as u32;
+				transactions
+					.try_push(TransactionInfo {
+						chunk_root: root,
+						size: data.len() as u32,
+						content_hash: content_hash.into(),
+						block_chunks: total_chunks,
+					})
+					.map_err(|_| Error::<T>::TooManyTransactions)
+			})?;
+			if let Some(namespace) = &namespace {
+				Self::note_namespace_bytes(index, namespace.clone(), data.len() as u32);
+			}
+			Self::note_stored(content_hash, index, namespace);
+			Ok((index, content_hash))
+		}
+
+		/// Record a store of the data with the given hash in `namespace` at `index` in the current
+		/// block. Identical data stored or renewed several times shares a single content index
+		/// entry, which is only removed once all of them have expired.
+		fn note_stored(content_hash: ContentHash, index: u32, namespace: Option<Namespace>) {
+			let block = <frame_system::Pallet<T>>::block_number();
+			let prev = ContentIndex::<T>::mutate(content_hash, |entry| {
+				let prev = entry.as_ref().map(|e| (e.block, e.index));
+				let refs = entry.as_ref().map_or(0, |e| e.refs).saturating_add(1);
+				let (namespace, stored_at) = match (namespace, entry.as_ref()) {
+					// A plain store of namespaced data doesn't take it out of its namespace, nor
+					// restart its retention
+					(None, Some(e)) if e.namespace.is_some() => (e.namespace.clone(), e.stored_at),
+					(namespace, _) => (namespace, block),
+				};
+				*entry = Some(ContentEntry { block, index, refs, namespace, stored_at });
+				prev
+			});
+			Self::note_copy(content_hash, prev, (block, index));
+		}
+
+		/// Record a renewal of the data with the given hash at `index` in the current block.
+		fn note_renewed(content_hash: ContentHash, index: u32) {
+			let block = <frame_system::Pallet<T>>::block_number();
+			let prev = ContentIndex::<T>::mutate(content_hash, |entry| match entry {
+				Some(e) => {
+					let prev = (e.block, e.index);
+					e.block = block;
+					e.index = index;
+					e.refs.saturating_inc();
+					Some(prev)
+				},
+				None => {
+					*entry = Some(ContentEntry {
+						block,
+						index,
+						refs: 1,
+						namespace: None,
+						stored_at: block,
+					});
+					None
+				},
 			});
+			Self::note_copy(content_hash, prev, (block, index));
+		}
+
+		/// Append the copy at `location` of the data with the given hash to its list of copies,
+		/// after `prev`, the previously most recent copy.
+		fn note_copy(
+			content_hash: ContentHash,
+			prev: Option<CopyLocation<BlockNumberFor<T>>>,
+			location: CopyLocation<BlockNumberFor<T>>,
+		) {
+			if let Some(prev) = prev {
+				ContentCopies::<T>::mutate(content_hash, prev, |links| {
+					if let Some(links) = links {
+						links.next = Some(location);
+					}
+				});
+			}
+			ContentCopies::<T>::insert(content_hash, location, CopyLinks { prev, next: None });
+		}
+
+		/// Drop the reference of the `store`/`renew` at `index` in `block` to the data with the
+		/// given hash from the content index. If the entry pointed to this copy, it is pointed to
+		/// the most recent remaining one. Returns `true` if this was the last reference and the
+		/// entry was removed.
+		fn unref_content(content_hash: ContentHash, block: BlockNumberFor<T>, index: u32) -> bool {
+			// Unlink the copy from its neighbours
+			let links = ContentCopies::<T>::take(content_hash, (block, index));
+			let (prev, next) = links.map_or((None, None), |links| (links.prev, links.next));
+			if let Some(prev) = prev {
+				ContentCopies::<T>::mutate(content_hash, prev, |links| {
+					if let Some(links) = links {
+						links.next = next;
+					}
+				});
+			}
+			if let Some(next) = next {
+				ContentCopies::<T>::mutate(content_hash, next, |links| {
+					if let Some(links) = links {
+						links.prev = prev;
+					}
+				});
+			}
+
+			ContentIndex::<T>::mutate_exists(content_hash, |entry| {
+				let Some(e) = entry else { return false };
+				e.refs.saturating_dec();
+				if e.refs == 0 {
+					*entry = None;
+					return true
+				}
+				// Only reached when retracting the most recent copy, as copies expire in order
+				if (e.block, e.index) == (block, index) {
+					if let Some((block, index)) = prev {
+						e.block = block;
+						e.index = index;
+					}
+				}
+				false
+			})
+		}
+
+		/// Record the signed account, if any, that submitted the `store`/`renew` at `index` in the
+		/// current block.
+		fn note_uploader(index: u32, uploader: Option<T::AccountId>) {
+			if let Some(uploader) = uploader {
+				let block = <frame_system::Pallet<T>>::block_number();
+				Uploaders::<T>::insert(block, index, uploader);
+			}
+		}
+
+		/// Returns `true` if `namespace` is configured and allows an item of the given size to be
+		/// stored.
+		fn namespace_ok(namespace: &Namespace, size: usize) -> bool {
+			NamespaceConfigs::<T>::get(namespace)
+				.map_or(false, |config| size <= config.max_item_size as usize) &&
+				Self::namespace_has_room(namespace, size)
+		}
+
+		/// Returns `true` if `size` more bytes can be kept in `namespace` without exceeding its
+		/// maximum total size. Always `true` if the namespace is not configured.
+		fn namespace_has_room(namespace: &Namespace, size: usize) -> bool {
+			NamespaceConfigs::<T>::get(namespace).map_or(true, |config| {
+				NamespaceBytes::<T>::get(namespace).saturating_add(size as u64) <=
+					config.max_total_bytes
+			})
+		}
+
+		/// Returns the namespace a renewal of the data with the given hash is counted in.
+		fn renewal_namespace(content_hash: ContentHash) -> Option<Namespace> {
+			ContentIndex::<T>::get(content_hash).and_then(|entry| entry.namespace)
+		}
+
+		/// Returns `true` if a renewal of `size` bytes of the data with the given hash fits in its
+		/// namespace.
+		fn renewal_has_room(content_hash: ContentHash, size: usize) -> bool {
+			Self::renewal_namespace(content_hash)
+				.map_or(true, |namespace| Self::namespace_has_room(&namespace, size))
 		}
 
-		/// Remove an expired authorization.
-		fn remove_expired_authorization(scope: AuthorizationScopeFor<T>) -> DispatchResult {
-			// In the case of a regular unsigned transaction, pre_dispatch should have checked that
-			// the authorization exists and has expired
-			let Some(authorization) = Authorizations::<T>::take(&scope) else {
-				return Err(Error::<T>::AuthorizationNotFound.into())
-			};
-			ensure!(Self::expired(authorization.expiration), Error::<T>::AuthorizationNotExpired);
-			Ok(())
+		/// Record that the `store`/`renew` at `index` in the current block keeps `size` bytes in
+		/// `namespace`.
+		fn note_namespace_bytes(index: u32, namespace: Namespace, size: u32) {
+			let block = <frame_system::Pallet<T>>::block_number();
+			NamespaceBytes::<T>::mutate(&namespace, |bytes| bytes.saturating_accrue(size as u64));
+			TransactionNamespaces::<T>::insert(block, index, namespace);
 		}
 
-		fn authorization_extent(scope: AuthorizationScopeFor<T>) -> AuthorizationExtent {
-			let Some(authorization) = Authorizations::<T>::get(&scope) else {
-				return AuthorizationExtent { transactions: 0, bytes: 0 }
-			};
-			if Self::expired(authorization.expiration) {
-				AuthorizationExtent { transactions: 0, bytes: 0 }
-			} else {
-				authorization.extent
-			}
+		/// Release the `size` bytes kept in a namespace by the `store`/`renew` at `index` in
+		/// `block`. Returns the namespace, if any.
+		fn release_namespace_bytes(
+			block: BlockNumberFor<T>,
+			index: u32,
+			size: u32,
+		) -> Option<Namespace> {
+			let namespace = TransactionNamespaces::<T>::take(block, index)?;
+			NamespaceBytes::<T>::mutate_exists(&namespace, |bytes| {
+				*bytes = bytes.map(|b| b.saturating_sub(size as u64)).filter(|b| *b != 0);
+			});
+			Some(namespace)
 		}
 
-		/// Returns the (unused and unexpired) authorization extent for the given account.
-		pub fn account_authorization_extent(who: T::AccountId) -> AuthorizationExtent {
-			Self::authorization_extent(AuthorizationScope::Account(who))
+		/// Returns `false` if the data with the given hash has been kept for the full retention
+		/// period of its namespace.
+		fn renewal_allowed(content_hash: ContentHash) -> bool {
+			let Some(entry) = ContentIndex::<T>::get(content_hash) else { return true };
+			let Some(config) =
+				entry.namespace.and_then(|namespace| NamespaceConfigs::<T>::get(namespace))
+			else {
+				return true
+			};
+			let now = frame_system::Pallet::<T>::block_number();
+			now < entry.stored_at.saturating_add(config.retention)
 		}
 
-		/// Returns the (unused and unexpired) authorization extent for the given content hash.
-		pub fn preimage_authorization_extent(hash: ContentHash) -> AuthorizationExtent {
-			Self::authorization_extent(AuthorizationScope::Preimage(hash))
+		/// Returns `true` if an upload of the given size and chunk count can be started.
+		fn upload_parameters_ok(size: u64, chunk_count: u32) -> bool {
+			let max_size = (chunk_count as u64).saturating_mul(T::MaxTransactionSize::get() as u64);
+			(chunk_count > 0) &&
+				(chunk_count <= T::MaxUploadChunks::get()) &&
+				(size >= chunk_count as u64) &&
+				(size <= max_size)
 		}
 
-		/// Returns the validity of the given call, signed by the given account.
-		///
-		/// This is equivalent to `validate_unsigned` but for signed transactions. It should be
-		/// called from a `SignedExtension` implementation.
-		pub fn validate_signed(who: &T::AccountId, call: &Call<T>) -> TransactionValidity {
-			Self::check_signed(who, call, CheckContext::Validate)?.ok_or(IMPOSSIBLE.into())
+		/// Returns `true` if a chunk of the given size is the next expected chunk of `upload`.
+		fn upload_chunk_ok(upload: &UploadSessionFor<T>, chunk: u32, size: usize) -> bool {
+			(chunk as usize == upload.chunk_hashes.len()) &&
+				(chunk < upload.chunk_count) &&
+				(upload.received.saturating_add(size as u64) <= upload.size)
 		}
 
-		/// Check the validity of the given call, signed by the given account, and consume
-		/// authorization for it.
-		///
-		/// This is equivalent to `pre_dispatch` but for signed transactions. It should be called
-		/// from a `SignedExtension` implementation.
-		pub fn pre_dispatch_signed(
-			who: &T::AccountId,
-			call: &Call<T>,
-		) -> Result<(), TransactionValidityError> {
-			Self::check_signed(who, call, CheckContext::PreDispatch).map(|_| ())
+		/// Returns `true` if all chunks of `upload` have been supplied.
+		fn upload_complete(upload: &UploadSessionFor<T>) -> bool {
+			(upload.chunk_hashes.len() == upload.chunk_count as usize) &&
+				(upload.received == upload.size)
 		}
 
-		/// Returns `true` if a blob of the given size can be stored.
-		fn data_size_ok(size: usize) -> bool {
-			(size > 0) && (size <= T::MaxTransactionSize::get() as usize)
+		/// Look up the upload session with the given ID, checking that it is owned by `who`.
+		fn owned_upload_session(
+			who: &T::AccountId,
+			session: UploadSessionId,
+		) -> Result<UploadSessionFor<T>, TransactionValidityError> {
+			match UploadSessions::<T>::get(session) {
+				Some(upload) if upload.owner == *who => Ok(upload),
+				_ => Err(UPLOAD_NOT_FOUND.into()),
+			}
 		}
 
-		/// Returns the [`TransactionInfo`] for the specified store/renew transaction.
+		/// Returns the [`TransactionInfo`] for the specified store/renew transaction, if it has not
+		/// expired or been retracted.
 		fn transaction_info(
 			block_number: BlockNumberFor<T>,
 			index: u32,
 		) -> Option<TransactionInfo> {
+			if Retracted::<T>::contains_key(block_number, index) {
+				return None
+			}
 			let transactions = Transactions::<T>::get(block_number)?;
 			transactions.into_iter().nth(index as usize)
 		}
@@ -734,11 +3236,36 @@ pub mod pallet {
 				.map_or(false, |len| len >= T::MaxBlockTransactions::get() as usize)
 		}
 
-		/// Check that authorization exists for data of the given size to be stored in a single
-		/// transaction. If `consume` is `true`, the authorization is consumed.
+		/// Returns `true` if `size` more bytes can't be stored or renewed in the current block.
+		fn block_bytes_full(size: usize) -> bool {
+			Self::block_bytes_remaining() < size as u64
+		}
+
+		/// Returns the number of bytes that can still be stored or renewed in the current block.
+		pub fn block_bytes_remaining() -> u64 {
+			(T::MaxBlockBytes::get() as u64).saturating_sub(BlockBytes::<T>::get())
+		}
+
+		/// Record that `size` bytes of data of the given class are stored or renewed in the
+		/// current block. Only normal data is limited by `MaxBlockBytes`.
+		fn note_block_bytes(size: u32, class: DispatchClass) -> DispatchResult {
+			BlockBytes::<T>::try_mutate(|bytes| {
+				let new_bytes = bytes.saturating_add(size as u64);
+				ensure!(
+					class != DispatchClass::Normal || new_bytes <= T::MaxBlockBytes::get() as u64,
+					Error::<T>::TooManyBytes
+				);
+				*bytes = new_bytes;
+				Ok(())
+			})
+		}
+
+		/// Check that authorization exists for data of the given size to be stored in the given
+		/// number of transactions. If `consume` is `true`, the authorization is consumed.
 		fn check_authorization(
 			scope: AuthorizationScopeFor<T>,
-			size: u32,
+			transactions: u32,
+			bytes: u64,
 			consume: bool,
 		) -> Result<(), TransactionValidityError> {
 			let consume_authorization = |maybe_authorization: &mut Option<Authorization<_>>| -> Result<(), TransactionValidityError> {
@@ -752,18 +3279,24 @@ pub mod pallet {
 				let transactions = authorization
 					.extent
 					.transactions
-					.checked_sub(1)
+					.checked_sub(transactions)
 					.ok_or(InvalidTransaction::Payment)?;
 				let bytes = authorization
 					.extent
 					.bytes
-					.checked_sub(size.into())
+					.checked_sub(bytes)
 					.ok_or(InvalidTransaction::Payment)?;
 
 				// Authorization is sufficient. Remove if _either_ no transactions left or no bytes
-				// left.
+				// left. Account authorizations are instead kept until they expire, as removing
+				// them may reap the account after its nonce has been incremented.
 				if transactions == 0 || bytes == 0 {
-					*maybe_authorization = None;
+					match scope {
+						AuthorizationScope::Account(_) => {
+							authorization.extent = AuthorizationExtent { transactions: 0, bytes: 0 }
+						},
+						AuthorizationScope::Preimage(_) => *maybe_authorization = None,
+					}
 				} else {
 					authorization.extent.transactions = transactions;
 					authorization.extent.bytes = bytes;
@@ -772,13 +3305,114 @@ pub mod pallet {
 			};
 
 			if consume {
-				Authorizations::<T>::mutate(&scope, consume_authorization)
+				let (existed, removed, rate, standing) =
+					Authorizations::<T>::try_mutate(&scope, |maybe_authorization| {
+						let existed = maybe_authorization.is_some();
+						let standing = Self::refresh_authorization(&scope, maybe_authorization);
+						consume_authorization(maybe_authorization)?;
+						let rate = Self::check_rate(&scope, bytes)?;
+						Ok::<_, TransactionValidityError>((
+							existed,
+							maybe_authorization.is_none(),
+							rate,
+							standing,
+						))
+					})?;
+				if let AuthorizationScope::Account(who) = &scope {
+					if let Some(rate) = rate {
+						AuthorizationRates::<T>::insert(who, rate);
+					}
+					if let Some(standing) = standing {
+						StandingAuthorizations::<T>::insert(who, standing);
+					}
+				}
+				// The authorization may have been created by a refresh
+				if !existed {
+					Self::authorization_added(&scope);
+				}
+				if removed {
+					Self::authorization_removed(&scope);
+				}
+				Ok(())
 			} else {
 				// Note we call consume_authorization on a temporary; the authorization in storage
 				// is untouched and doesn't actually get consumed
-				let mut authorization = Authorizations::<T>::get(&scope);
-				consume_authorization(&mut authorization)
+				let mut authorization = Self::refreshed_authorization(&scope);
+				consume_authorization(&mut authorization)?;
+				Self::check_rate(&scope, bytes).map(|_| ())
+			}
+		}
+
+		/// Check that `bytes` more bytes of the authorization with the given scope may be used in
+		/// the current period without exceeding its rate limit. Returns the updated rate to store
+		/// if the bytes are used, or `None` if the authorization is not rate limited.
+		fn check_rate(
+			scope: &AuthorizationScopeFor<T>,
+			bytes: u64,
+		) -> Result<Option<AuthorizationRateFor<T>>, TransactionValidityError> {
+			let AuthorizationScope::Account(who) = scope else { return Ok(None) };
+			let Some(mut rate) = AuthorizationRates::<T>::get(who) else { return Ok(None) };
+			let now = frame_system::Pallet::<T>::block_number();
+			let period_start = now - now % rate.limit.period;
+			if rate.period_start != period_start {
+				rate.period_start = period_start;
+				rate.used = 0;
+			}
+			rate.used = rate
+				.used
+				.checked_add(bytes)
+				.filter(|used| *used <= rate.limit.bytes)
+				.ok_or(RATE_LIMITED)?;
+			Ok(Some(rate))
+		}
+
+		/// Returns the authorization with the given scope, as it would be after being refreshed to
+		/// its standing quota.
+		fn refreshed_authorization(
+			scope: &AuthorizationScopeFor<T>,
+		) -> Option<AuthorizationFor<T>> {
+			let mut authorization = Authorizations::<T>::get(scope);
+			Self::refresh_authorization(scope, &mut authorization);
+			authorization
+		}
+
+		/// Refresh an account authorization to the account's standing quota, if it has one that
+		/// has not yet been applied in the current period. Each extent is raised to the quota and
+		/// the expiration pushed back to the end of the period; an expired authorization is
+		/// replaced. Returns the updated standing authorization to store if the authorization
+		/// was refreshed.
+		fn refresh_authorization(
+			scope: &AuthorizationScopeFor<T>,
+			maybe_authorization: &mut Option<AuthorizationFor<T>>,
+		) -> Option<StandingAuthorizationFor<T>> {
+			let AuthorizationScope::Account(who) = scope else { return None };
+			let mut standing = StandingAuthorizations::<T>::get(who)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			let period_start = now - now % standing.quota.period;
+			if standing.refreshed == Some(period_start) {
+				return None
+			}
+			standing.refreshed = Some(period_start);
+
+			let quota = &standing.quota;
+			let expiration = period_start.saturating_add(quota.period);
+			match maybe_authorization {
+				Some(authorization) if !Self::expired(authorization.expiration) => {
+					let extent = &mut authorization.extent;
+					extent.transactions = extent.transactions.max(quota.transactions);
+					extent.bytes = extent.bytes.max(quota.bytes);
+					authorization.expiration = authorization.expiration.max(expiration);
+				},
+				_ =>
+					*maybe_authorization = Some(Authorization {
+						extent: AuthorizationExtent {
+							transactions: quota.transactions,
+							bytes: quota.bytes,
+						},
+						expiration,
+					}),
 			}
+			Some(standing)
 		}
 
 		/// Check that authorization with the given scope exists in storage but has expired.
@@ -798,13 +3432,16 @@ pub mod pallet {
 		fn check_store_renew_unsigned(
 			size: usize,
 			hash: impl FnOnce() -> ContentHash,
+			class: DispatchClass,
 			context: CheckContext,
 		) -> Result<Option<ValidTransaction>, TransactionValidityError> {
 			if !Self::data_size_ok(size) {
 				return Err(BAD_DATA_SIZE.into())
 			}
 
-			if Self::block_transactions_full() {
+			if Self::block_transactions_full() ||
+				(class == DispatchClass::Normal && Self::block_bytes_full(size))
+			{
 				return Err(InvalidTransaction::ExhaustsResources.into())
 			}
 
@@ -812,14 +3449,18 @@ pub mod pallet {
 
 			Self::check_authorization(
 				AuthorizationScope::Preimage(hash),
-				size as u32,
+				1,
+				size as u64,
 				context.consume_authorization(),
 			)?;
 
 			Ok(context.want_valid_transaction().then(|| {
 				ValidTransaction::with_tag_prefix("TransactionStorageStoreRenew")
 					.and_provides(hash)
-					.priority(T::StoreRenewPriority::get())
+					.priority(match class {
+						DispatchClass::Operational => T::OperationalStorePriority::get(),
+						_ => T::StoreRenewPriority::get(),
+					})
 					.longevity(T::StoreRenewLongevity::get())
 					.into()
 			}))
@@ -833,13 +3474,59 @@ pub mod pallet {
 				Call::<T>::store { data } => Self::check_store_renew_unsigned(
 					data.len(),
 					|| sp_io::hashing::blake2_256(data),
+					DispatchClass::Normal,
+					context,
+				),
+				Call::<T>::store_operational { data } => Self::check_store_renew_unsigned(
+					data.len(),
+					|| sp_io::hashing::blake2_256(data),
+					DispatchClass::Operational,
 					context,
 				),
 				Call::<T>::renew { block, index } => {
 					let info = Self::transaction_info(*block, *index).ok_or(RENEWED_NOT_FOUND)?;
+					if !Self::renewal_allowed(info.content_hash.into()) {
+						return Err(RETENTION_EXCEEDED.into())
+					}
+					if !Self::renewal_has_room(info.content_hash.into(), info.size as usize) {
+						return Err(BAD_NAMESPACE.into())
+					}
 					Self::check_store_renew_unsigned(
 						info.size as usize,
 						|| info.content_hash.into(),
+						DispatchClass::Normal,
+						context,
+					)
+				},
+				Call::<T>::store_in_namespace { namespace, data } => {
+					if !Self::namespace_ok(namespace, data.len()) {
+						return Err(BAD_NAMESPACE.into())
+					}
+					Self::check_store_renew_unsigned(
+						data.len(),
+						|| sp_io::hashing::blake2_256(data),
+						DispatchClass::Normal,
+						context,
+					)
+				},
+				Call::<T>::store_with_attestation { attestation, data } => {
+					let content_hash = sp_io::hashing::blake2_256(data);
+					if !attestation.verify(&content_hash) {
+						return Err(BAD_ATTESTATION.into())
+					}
+					Self::check_store_renew_unsigned(
+						data.len(),
+						|| content_hash,
+						DispatchClass::Normal,
+						context,
+					)
+				},
+				Call::<T>::store_encoded { encoding, data } => {
+					encoding::decoded_size(*encoding, data).ok_or(BAD_ENCODING)?;
+					Self::check_store_renew_unsigned(
+						data.len(),
+						|| sp_io::hashing::blake2_256(data),
+						DispatchClass::Normal,
 						context,
 					)
 				},
@@ -871,17 +3558,293 @@ pub mod pallet {
 			}
 		}
 
+		/// Check a signed call to start, resume or complete a chunked upload. These calls do not
+		/// store any data themselves. Starting an upload consumes one transaction, and no bytes, of
+		/// the signer's account authorization; resuming or completing one consumes nothing.
+		fn check_upload_signed(
+			who: &T::AccountId,
+			call: &Call<T>,
+			context: CheckContext,
+		) -> Result<Option<ValidTransaction>, TransactionValidityError> {
+			match call {
+				Call::<T>::begin_upload { size, chunk_count, .. } => {
+					if !Self::upload_parameters_ok(*size, *chunk_count) {
+						return Err(BAD_UPLOAD_PARAMETERS.into())
+					}
+					// The signer must be authorized to supply all of the chunks. Starting the
+					// upload uses one more transaction, so that uploads can't be started for free
+					let transactions = chunk_count.saturating_add(1);
+					let (scope, _) = Self::account_scope(who, transactions, *size);
+					Self::check_authorization(scope.clone(), transactions, *size, false)?;
+					Self::check_authorization(scope, 1, 0, context.consume_authorization())?;
+				},
+				Call::<T>::resume_upload { session } => {
+					Self::owned_upload_session(who, *session)?;
+				},
+				Call::<T>::finalize_upload { session } => {
+					let upload = Self::owned_upload_session(who, *session)?;
+					if !Self::upload_complete(&upload) {
+						return Err(UPLOAD_INCOMPLETE.into())
+					}
+				},
+				_ => return Err(InvalidTransaction::Call.into()),
+			}
+
+			Ok(context.want_valid_transaction().then(|| ValidTransaction {
+				priority: T::StoreRenewPriority::get(),
+				longevity: T::StoreRenewLongevity::get(),
+				..Default::default()
+			}))
+		}
+
+		/// Check a signed call to retract data. No authorization is consumed.
+		fn check_retract_signed(
+			who: &T::AccountId,
+			block: BlockNumberFor<T>,
+			index: u32,
+			context: CheckContext,
+		) -> Result<Option<ValidTransaction>, TransactionValidityError> {
+			if Self::transaction_info(block, index).is_none() {
+				return Err(RETRACTED_NOT_FOUND.into())
+			}
+			if Uploaders::<T>::get(block, index).as_ref() != Some(who) {
+				return Err(InvalidTransaction::BadSigner.into())
+			}
+
+			Ok(context.want_valid_transaction().then(|| ValidTransaction {
+				priority: T::StoreRenewPriority::get(),
+				longevity: T::StoreRenewLongevity::get(),
+				..Default::default()
+			}))
+		}
+
+		/// Check a signed call to transfer authorization. The transfer itself consumes no
+		/// authorization beyond what is transferred.
+		fn check_transfer_signed(
+			who: &T::AccountId,
+			transactions: u32,
+			bytes: u64,
+			context: CheckContext,
+		) -> Result<Option<ValidTransaction>, TransactionValidityError> {
+			Self::check_authorization(
+				AuthorizationScope::Account(who.clone()),
+				transactions,
+				bytes,
+				false,
+			)?;
+
+			Ok(context.want_valid_transaction().then(|| ValidTransaction {
+				priority: T::StoreRenewPriority::get(),
+				longevity: T::StoreRenewLongevity::get(),
+				..Default::default()
+			}))
+		}
+
+		/// Check a signed call to delegate authorization or revoke a delegation. No authorization
+		/// is consumed.
+		fn check_delegation_signed(
+			who: &T::AccountId,
+			call: &Call<T>,
+			context: CheckContext,
+		) -> Result<Option<ValidTransaction>, TransactionValidityError> {
+			match call {
+				Call::<T>::delegate { .. } => {
+					let scope = AuthorizationScope::Account(who.clone());
+					Self::check_authorization(scope, 0, 0, false)?;
+				},
+				Call::<T>::revoke_delegation { who: delegate } => {
+					let delegation = Delegations::<T>::get(delegate).ok_or(DELEGATION_NOT_FOUND)?;
+					if *who != delegation.owner && who != delegate {
+						return Err(DELEGATION_NOT_FOUND.into())
+					}
+				},
+				_ => return Err(InvalidTransaction::Call.into()),
+			}
+
+			Ok(context.want_valid_transaction().then(|| ValidTransaction {
+				priority: T::StoreRenewPriority::get(),
+				longevity: T::StoreRenewLongevity::get(),
+				..Default::default()
+			}))
+		}
+
+		/// Check a signed call to claim, point or release a name. Claiming a name uses one
+		/// transaction and the length of the name in bytes of the signer's account authorization.
+		fn check_name_signed(
+			who: &T::AccountId,
+			call: &Call<T>,
+			context: CheckContext,
+		) -> Result<Option<ValidTransaction>, TransactionValidityError> {
+			let owned =
+				|name: &Name| Names::<T>::get(name).map_or(false, |pointer| pointer.owner == *who);
+			match call {
+				Call::<T>::claim_name { name, content_hash } => {
+					if Names::<T>::contains_key(name) ||
+						!ContentIndex::<T>::contains_key(content_hash)
+					{
+						return Err(BAD_NAME.into())
+					}
+					Self::check_authorization(
+						AuthorizationScope::Account(who.clone()),
+						1,
+						name.len() as u64,
+						context.consume_authorization(),
+					)?;
+				},
+				Call::<T>::point_name { name, content_hash } =>
+					if !owned(name) || !ContentIndex::<T>::contains_key(content_hash) {
+						return Err(BAD_NAME.into())
+					},
+				Call::<T>::release_name { name } =>
+					if !owned(name) {
+						return Err(BAD_NAME.into())
+					},
+				_ => return Err(InvalidTransaction::Call.into()),
+			}
+
+			Ok(context.want_valid_transaction().then(|| ValidTransaction {
+				priority: T::StoreRenewPriority::get(),
+				longevity: T::StoreRenewLongevity::get(),
+				..Default::default()
+			}))
+		}
+
+		/// Check a signed call to commit to publishing data.
+		fn check_commit_signed(
+			who: &T::AccountId,
+			content_hash: ContentHash,
+			context: CheckContext,
+		) -> Result<Option<ValidTransaction>, TransactionValidityError> {
+			if !Self::commitment_allowed(who, content_hash) {
+				return Err(BAD_COMMITMENT.into())
+			}
+			Self::check_authorization(
+				AuthorizationScope::Account(who.clone()),
+				1,
+				content_hash.len() as u64,
+				context.consume_authorization(),
+			)?;
+
+			Ok(context.want_valid_transaction().then(|| {
+				ValidTransaction::with_tag_prefix("TransactionStorageCommit")
+					.and_provides((who, content_hash))
+					.priority(T::StoreRenewPriority::get())
+					.longevity(T::StoreRenewLongevity::get())
+					.into()
+			}))
+		}
+
+		/// Check a signed call to pin data. Pinning uses one transaction and the size of the data
+		/// in bytes of the signer's account authorization.
+		fn check_pin_signed(
+			who: &T::AccountId,
+			content_hash: ContentHash,
+			context: CheckContext,
+		) -> Result<Option<ValidTransaction>, TransactionValidityError> {
+			let (block, index) = Self::content_location(content_hash).ok_or(RENEWED_NOT_FOUND)?;
+			let info = Self::transaction_info(block, index).ok_or(RENEWED_NOT_FOUND)?;
+			let size = info.size as usize;
+			if !Self::data_size_ok(size) {
+				return Err(BAD_DATA_SIZE.into())
+			}
+			if !Self::renewal_allowed(content_hash) {
+				return Err(RETENTION_EXCEEDED.into())
+			}
+			if !Self::renewal_has_room(content_hash, size) {
+				return Err(BAD_NAMESPACE.into())
+			}
+			if Self::block_transactions_full() || Self::block_bytes_full(size) {
+				return Err(InvalidTransaction::ExhaustsResources.into())
+			}
+			Self::check_authorization(
+				AuthorizationScope::Account(who.clone()),
+				1,
+				size as u64,
+				context.consume_authorization(),
+			)?;
+
+			Ok(context.want_valid_transaction().then(|| ValidTransaction {
+				priority: T::StoreRenewPriority::get(),
+				longevity: T::StoreRenewLongevity::get(),
+				..Default::default()
+			}))
+		}
+
 		fn check_signed(
 			who: &T::AccountId,
 			call: &Call<T>,
 			context: CheckContext,
 		) -> Result<Option<ValidTransaction>, TransactionValidityError> {
-			let size = match call {
-				Call::<T>::store { data } => data.len(),
+			// Size charged against account authorization, if not the size of the data
+			let mut account_size = None;
+			let (size, content_hash) = match call {
+				Call::<T>::store { data } => (data.len(), Some(sp_io::hashing::blake2_256(data))),
 				Call::<T>::renew { block, index } => {
 					let info = Self::transaction_info(*block, *index).ok_or(RENEWED_NOT_FOUND)?;
-					info.size as usize
+					if !Self::renewal_allowed(info.content_hash.into()) {
+						return Err(RETENTION_EXCEEDED.into())
+					}
+					if !Self::renewal_has_room(info.content_hash.into(), info.size as usize) {
+						return Err(BAD_NAMESPACE.into())
+					}
+					(info.size as usize, Some(info.content_hash.into()))
+				},
+				Call::<T>::store_in_namespace { namespace, data } => {
+					if !Self::namespace_ok(namespace, data.len()) {
+						return Err(BAD_NAMESPACE.into())
+					}
+					(data.len(), Some(sp_io::hashing::blake2_256(data)))
+				},
+				Call::<T>::store_with_attestation { attestation, data } => {
+					let content_hash = sp_io::hashing::blake2_256(data);
+					if !attestation.verify(&content_hash) {
+						return Err(BAD_ATTESTATION.into())
+					}
+					(data.len(), Some(content_hash))
+				},
+				Call::<T>::reveal { data } => {
+					let content_hash = sp_io::hashing::blake2_256(data);
+					if Self::revealable_commitment(who, content_hash).is_none() {
+						return Err(BAD_COMMITMENT.into())
+					}
+					(data.len(), Some(content_hash))
+				},
+				Call::<T>::store_encoded { encoding, data } => {
+					let decoded_size =
+						encoding::decoded_size(*encoding, data).ok_or(BAD_ENCODING)?;
+					account_size = Some(decoded_size.max(data.len() as u64));
+					(data.len(), Some(sp_io::hashing::blake2_256(data)))
 				},
+				Call::<T>::upload_chunk { session, chunk, data } => {
+					let upload = Self::owned_upload_session(who, *session)?;
+					if Self::expired(upload.expires_at) {
+						return Err(UPLOAD_TIMED_OUT.into())
+					}
+					if !Self::upload_chunk_ok(&upload, *chunk, data.len()) {
+						return Err(UNEXPECTED_UPLOAD_CHUNK.into())
+					}
+					(data.len(), None)
+				},
+				Call::<T>::begin_upload { .. } |
+				Call::<T>::resume_upload { .. } |
+				Call::<T>::finalize_upload { .. } =>
+					return Self::check_upload_signed(who, call, context),
+				Call::<T>::retract { block, index } =>
+					return Self::check_retract_signed(who, *block, *index, context),
+				// Operational data requires preimage authorization, whoever submits it
+				Call::<T>::store_operational { .. } => return Self::check_unsigned(call, context),
+				Call::<T>::transfer_quota { transactions, bytes, .. } =>
+					return Self::check_transfer_signed(who, *transactions, *bytes, context),
+				Call::<T>::delegate { .. } | Call::<T>::revoke_delegation { .. } =>
+					return Self::check_delegation_signed(who, call, context),
+				Call::<T>::claim_name { .. } |
+				Call::<T>::point_name { .. } |
+				Call::<T>::release_name { .. } =>
+					return Self::check_name_signed(who, call, context),
+				Call::<T>::pin { content_hash } =>
+					return Self::check_pin_signed(who, *content_hash, context),
+				Call::<T>::commit { content_hash } =>
+					return Self::check_commit_signed(who, *content_hash, context),
 				_ => return Err(InvalidTransaction::Call.into()),
 			};
 
@@ -889,15 +3852,33 @@ pub mod pallet {
 				return Err(BAD_DATA_SIZE.into())
 			}
 
-			if Self::block_transactions_full() {
+			if Self::block_transactions_full() || Self::block_bytes_full(size) {
 				return Err(InvalidTransaction::ExhaustsResources.into())
 			}
 
-			Self::check_authorization(
-				AuthorizationScope::Account(who.clone()),
-				size as u32,
-				context.consume_authorization(),
-			)?;
+			// Data with a preimage authorization may be submitted by any account, without using
+			// the account's own authorization
+			let preimage_authorized = |hash| {
+				Self::check_authorization(AuthorizationScope::Preimage(hash), 1, size as u64, false)
+					.is_ok()
+			};
+			let (scope, bytes, delegated) = match content_hash {
+				Some(hash) if preimage_authorized(hash) =>
+					(AuthorizationScope::Preimage(hash), size as u64, false),
+				_ => {
+					let bytes = account_size.unwrap_or(size as u64);
+					let (scope, delegated) = Self::account_scope(who, 1, bytes);
+					(scope, bytes, delegated)
+				},
+			};
+			Self::check_authorization(scope, 1, bytes, context.consume_authorization())?;
+			if delegated && context.consume_authorization() {
+				Delegations::<T>::mutate(who, |maybe_delegation| {
+					if let Some(delegation) = maybe_delegation {
+						delegation.bytes_used.saturating_accrue(bytes);
+					}
+				});
+			}
 
 			Ok(context.want_valid_transaction().then(|| ValidTransaction {
 				priority: T::StoreRenewPriority::get(),
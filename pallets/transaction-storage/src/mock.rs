@@ -18,15 +18,17 @@
 //! Test environment for transaction-storage pallet.
 
 use crate::{
-	self as pallet_transaction_storage, TransactionStorageProof, DEFAULT_MAX_BLOCK_TRANSACTIONS,
-	DEFAULT_MAX_TRANSACTION_SIZE,
+	self as pallet_transaction_storage, Namespace, OnDataExpired, TransactionStorageProof,
+	ValidatePayload, DEFAULT_MAX_BLOCK_TRANSACTIONS, DEFAULT_MAX_TRANSACTION_SIZE,
 };
 use frame_support::{
 	parameter_types,
-	traits::{ConstU16, ConstU32, ConstU64, OnFinalize, OnInitialize},
+	traits::{ConstU16, ConstU32, ConstU64, OnFinalize, OnInitialize, Randomness},
+	weights::Weight,
 };
 use frame_system::{pallet_prelude::BlockNumberFor, EnsureRoot};
 use sp_core::H256;
+use sp_keystore::{testing::MemoryKeystore, KeystoreExt};
 use sp_runtime::{
 	traits::{BlakeTwo256, IdentityLookup},
 	transaction_validity::{TransactionLongevity, TransactionPriority},
@@ -73,10 +75,47 @@ impl frame_system::Config for Test {
 parameter_types! {
 	pub const StoragePeriod: BlockNumberFor<Test> = 10;
 	pub const AuthorizationPeriod: BlockNumberFor<Test> = 10;
-	pub const StoreRenewPriority: TransactionPriority = TransactionPriority::max_value();
+	pub const StoreRenewPriority: TransactionPriority = TransactionPriority::max_value() - 1;
 	pub const StoreRenewLongevity: TransactionLongevity = 10;
+	pub const OperationalStorePriority: TransactionPriority = TransactionPriority::max_value();
 	pub const RemoveExpiredAuthorizationPriority: TransactionPriority = TransactionPriority::max_value();
 	pub const RemoveExpiredAuthorizationLongevity: TransactionLongevity = 10;
+	pub static ExpiredData: Vec<([u8; 32], Option<Namespace>, Option<u64>)> = vec![];
+	pub static ProofRandomness: Option<H256> = None;
+}
+
+/// Records the data passed to [`OnDataExpired`] in [`ExpiredData`].
+pub struct RecordExpiredData;
+
+impl OnDataExpired<u64> for RecordExpiredData {
+	fn on_data_expired(
+		content_hash: &[u8; 32],
+		namespace: Option<&Namespace>,
+		uploader: Option<&u64>,
+	) -> Weight {
+		let mut expired = ExpiredData::get();
+		expired.push((*content_hash, namespace.cloned(), uploader.copied()));
+		ExpiredData::set(expired);
+		Weight::zero()
+	}
+}
+
+/// Requires data in the `wasm` namespace to start with the Wasm magic number.
+pub struct RequireWasm;
+
+impl ValidatePayload for RequireWasm {
+	fn validate_payload(namespace: &Namespace, data: &[u8]) -> bool {
+		namespace.as_slice() != b"wasm" || data.starts_with(b"\0asm")
+	}
+}
+
+/// Returns [`ProofRandomness`] regardless of the subject.
+pub struct TestRandomness;
+
+impl Randomness<Option<H256>, u64> for TestRandomness {
+	fn random(_subject: &[u8]) -> (Option<H256>, u64) {
+		(ProofRandomness::get(), System::block_number())
+	}
 }
 
 impl pallet_transaction_storage::Config for Test {
@@ -89,13 +128,24 @@ impl pallet_transaction_storage::Config for Test {
 	type Authorizer = EnsureRoot<Self::AccountId>;
 	type StoreRenewPriority = StoreRenewPriority;
 	type StoreRenewLongevity = StoreRenewLongevity;
+	type OperationalStorePriority = OperationalStorePriority;
 	type RemoveExpiredAuthorizationPriority = RemoveExpiredAuthorizationPriority;
 	type RemoveExpiredAuthorizationLongevity = RemoveExpiredAuthorizationLongevity;
+	type MaxUploadChunks = ConstU32<4>;
+	type MaxBlockBytes = ConstU32<{ DEFAULT_MAX_TRANSACTION_SIZE }>;
+	type UploadTimeout = ConstU64<5>;
+	type OnDataExpired = RecordExpiredData;
+	type PayloadValidator = RequireWasm;
+	type UsagePeriod = ConstU64<5>;
+	type RevealPeriod = ConstU64<5>;
+	type Randomness = TestRandomness;
 }
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
 	let t = RuntimeGenesisConfig { system: Default::default() }.build_storage().unwrap();
-	t.into()
+	let mut ext: sp_io::TestExternalities = t.into();
+	ext.register_extension(KeystoreExt::new(MemoryKeystore::new()));
+	ext
 }
 
 pub fn run_to_block(n: u64, f: impl Fn() -> Option<TransactionStorageProof>) {
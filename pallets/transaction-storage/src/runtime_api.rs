@@ -0,0 +1,89 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for the transaction storage pallet.
+
+use crate::{AuthorizationStatus, ChunkProof, DataStatus, Namespace, StorageUsage, UploadReceipt};
+use codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	/// API for querying the transaction storage pallet.
+	pub trait TransactionStorageApi<AccountId, BlockNumber>
+	where
+		AccountId: Codec,
+		BlockNumber: Codec,
+	{
+		/// Returns the remaining storage authorization of `who`, or `None` if `who` has no
+		/// unexpired authorization.
+		fn authorization_status(who: AccountId) -> Option<AuthorizationStatus<BlockNumber>>;
+
+		/// Returns the block number and index within the block of the most recent `store` or
+		/// `renew` of the data with the given BLAKE2b-256 hash, or `None` if no such data is being
+		/// kept.
+		fn lookup(hash: [u8; 32]) -> Option<(BlockNumber, u32)>;
+
+		/// Returns the number of bytes that can still be stored or renewed in the block being
+		/// built.
+		fn block_bytes_remaining() -> u64;
+
+		/// Returns the availability status of the data with the given BLAKE2b-256 hash, or of the
+		/// chunked upload with the given root.
+		fn data_status(hash: [u8; 32]) -> DataStatus<BlockNumber>;
+
+		/// Returns the storage key of the [`TransactionInfo`](crate::TransactionInfo)s of the data
+		/// stored or renewed in `block`. A read proof of this key (see `state_getReadProof`)
+		/// against the state root of a later block, until the data expires, proves that data with
+		/// a given hash and chunk root was stored in `block`.
+		fn transactions_key(block: BlockNumber) -> Vec<u8>;
+
+		/// Returns the BLAKE2b-256 hash of the data the given name points to, or `None` if the
+		/// name has not been claimed. The data may no longer be kept.
+		fn resolve_name(name: Vec<u8>) -> Option<[u8; 32]>;
+
+		/// Returns the total size of the data being kept, when it expires, and how much of each
+		/// namespace's maximum total size is used.
+		fn storage_usage() -> StorageUsage<BlockNumber>;
+
+		/// Returns the receipt of the completed chunked upload with the given root, or `None` if
+		/// there is no such upload or its last chunk has expired.
+		fn upload_receipt(root: [u8; 32]) -> Option<UploadReceipt<BlockNumber>>;
+
+		/// Returns a proof of the hash of chunk `chunk` of the completed chunked upload with the
+		/// given root, so that the chunk can be verified against the root on its own.
+		fn upload_chunk_proof(root: [u8; 32], chunk: u32) -> Option<ChunkProof>;
+
+		/// Returns the canonical publication block of the data with the given BLAKE2b-256 hash:
+		/// the block it was committed to in, if it was published with `commit` and `reveal`.
+		fn commit_block(hash: [u8; 32]) -> Option<BlockNumber>;
+
+		/// Returns the randomness selecting the chunk to prove in the storage proof of the next
+		/// block, or `None` if the chunk is selected with the hash of the block this is called
+		/// at.
+		fn storage_proof_randomness() -> Option<[u8; 32]>;
+
+		/// Returns the BLAKE2b-256 hash, namespace and expiry block of the data that expires
+		/// within the next `blocks` blocks, in order of expiry. Results are paginated: at most
+		/// `limit` entries (capped to [`MAX_EXPIRING_DATA`](crate::MAX_EXPIRING_DATA)) are
+		/// returned after skipping the first `skip`.
+		fn expiring_within(
+			blocks: BlockNumber,
+			skip: u32,
+			limit: u32,
+		) -> Vec<([u8; 32], Option<Namespace>, BlockNumber)>;
+	}
+}
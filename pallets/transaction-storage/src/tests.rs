@@ -19,27 +19,50 @@
 
 use super::{
 	mock::{
-		new_test_ext, run_to_block, RuntimeCall, RuntimeEvent, RuntimeOrigin, System, Test,
-		TransactionStorage,
+		new_test_ext, run_to_block, ExpiredData, OperationalStorePriority, ProofRandomness,
+		RuntimeCall, RuntimeEvent, RuntimeOrigin, System, Test, TransactionStorage,
 	},
-	AuthorizationExtent, AuthorizationScope, Event, AUTHORIZATION_NOT_EXPIRED,
-	DEFAULT_MAX_TRANSACTION_SIZE,
+	Attestation, AuthorizationDetails, AuthorizationExtent, AuthorizationScope,
+	AuthorizationStatus, ContentEncoding, ContentHash, DataStatus, Event, Name, Namespace,
+	NamespaceConfig, RateLimit, StandingQuota, StorageUsage, TransactionInfo, UploadReceipt,
+	AUTHORIZATION_NOT_EXPIRED, BAD_ATTESTATION, BAD_COMMITMENT, BAD_ENCODING, BAD_NAME,
+	BAD_NAMESPACE, DEFAULT_MAX_TRANSACTION_SIZE, DELEGATION_NOT_FOUND, RATE_LIMITED,
+	RENEWED_NOT_FOUND, RETENTION_EXCEEDED, UNEXPECTED_UPLOAD_CHUNK, UPLOAD_INCOMPLETE,
+	UPLOAD_NOT_FOUND, UPLOAD_TIMED_OUT,
 };
-use frame_support::{assert_noop, assert_ok};
-use sp_core::blake2_256;
+use codec::Decode;
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::{DispatchClass, GetDispatchInfo},
+	traits::{Get, Hooks},
+	weights::Weight,
+	BoundedVec,
+};
+use sp_core::{blake2_256, sr25519, Pair, H256};
 use sp_runtime::{
 	traits::{Dispatchable, ValidateUnsigned},
-	transaction_validity::InvalidTransaction,
+	transaction_validity::{InvalidTransaction, TransactionSource},
+	DispatchError,
 };
-use sp_transaction_storage_proof::registration::build_proof;
+use sp_transaction_storage_proof::{encode_index, registration::build_proof};
 
 type Call = super::Call<Test>;
 type Error = super::Error<Test>;
 
+type Attestations = super::Attestations<Test>;
+type AuthorizationRates = super::AuthorizationRates<Test>;
 type Authorizations = super::Authorizations<Test>;
 type BlockTransactions = super::BlockTransactions<Test>;
 type ChunkCount = super::ChunkCount<Test>;
+type ContentCopies = super::ContentCopies<Test>;
+type ContentIndex = super::ContentIndex<Test>;
+type Delegations = super::Delegations<Test>;
+type ExpiredContent = super::ExpiredContent<Test>;
+type NamespaceBytes = super::NamespaceBytes<Test>;
+type Retracted = super::Retracted<Test>;
 type Transactions = super::Transactions<Test>;
+type UploadSessions = super::UploadSessions<Test>;
+type Uploaders = super::Uploaders<Test>;
 
 const MAX_DATA_SIZE: u32 = DEFAULT_MAX_TRANSACTION_SIZE;
 
@@ -72,6 +95,356 @@ fn discards_data() {
 	});
 }
 
+#[test]
+fn expired_data_emits_events() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		let data = vec![vec![1u8; 2000], vec![2u8; 3000]];
+		for d in &data {
+			assert_ok!(TransactionStorage::store(RuntimeOrigin::none(), d.clone()));
+		}
+		let proof_provider = || {
+			let block_num = System::block_number();
+			if block_num == 11 {
+				let parent_hash = System::parent_hash();
+				Some(build_proof(parent_hash.as_ref(), data.clone()).unwrap())
+			} else {
+				None
+			}
+		};
+		run_to_block(11, proof_provider);
+		let expired = |index: u32| {
+			RuntimeEvent::TransactionStorage(Event::Expired {
+				block: 1,
+				index,
+				content_hash: blake2_256(&data[index as usize]),
+				namespace: None,
+				uploader: None,
+			})
+		};
+		assert!(!System::events().iter().any(|record| record.event == expired(0)));
+		run_to_block(12, proof_provider);
+		System::assert_has_event(expired(0));
+		System::assert_has_event(expired(1));
+	});
+}
+
+#[test]
+fn notifies_expired_data() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		let namespace: Namespace = b"test".to_vec().try_into().unwrap();
+		let config = NamespaceConfig { max_item_size: 2000, retention: 100, max_total_bytes: 4000 };
+		assert_ok!(TransactionStorage::set_namespace_config(
+			RuntimeOrigin::root(),
+			namespace.clone(),
+			Some(config)
+		));
+		let data = vec![0u8; 2000];
+		let hash = blake2_256(&data);
+		assert_ok!(TransactionStorage::store_in_namespace(
+			RuntimeOrigin::signed(1),
+			namespace.clone(),
+			data.clone()
+		));
+		run_to_block(2, || None);
+		assert_ok!(TransactionStorage::renew(RuntimeOrigin::signed(2), 1, 0));
+
+		let proof_provider = || {
+			let block_num = System::block_number();
+			if block_num == 11 || block_num == 12 {
+				let parent_hash = System::parent_hash();
+				Some(build_proof(parent_hash.as_ref(), vec![data.clone()]).unwrap())
+			} else {
+				None
+			}
+		};
+		run_to_block(12, proof_provider);
+		System::assert_has_event(RuntimeEvent::TransactionStorage(Event::Expired {
+			block: 1,
+			index: 0,
+			content_hash: hash,
+			namespace: Some(namespace.clone()),
+			uploader: Some(1),
+		}));
+		// Still kept under the renewal
+		assert!(ExpiredData::get().is_empty());
+
+		run_to_block(13, proof_provider);
+		assert_eq!(ExpiredData::get(), vec![(hash, Some(namespace), Some(2))]);
+	});
+}
+
+#[test]
+fn stored_event_contains_cid() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		let data = vec![7u8; 2000];
+		assert_ok!(TransactionStorage::store(RuntimeOrigin::none(), data.clone()));
+		let mut expected = vec![0x01, 0x55, 0xa0, 0xe4, 0x02, 0x20];
+		expected.extend_from_slice(&blake2_256(&data));
+		System::assert_last_event(RuntimeEvent::TransactionStorage(Event::Stored {
+			index: 0,
+			cid: expected.try_into().unwrap(),
+			namespace: None,
+		}));
+	});
+}
+
+#[test]
+fn limits_bytes_per_block() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		let who = 1;
+		assert_ok!(TransactionStorage::authorize_account(
+			RuntimeOrigin::root(),
+			who,
+			3,
+			MAX_DATA_SIZE as u64 * 2
+		));
+		assert_ok!(TransactionStorage::store(RuntimeOrigin::none(), vec![0u8; 2000]));
+		assert_eq!(TransactionStorage::block_bytes_remaining(), MAX_DATA_SIZE as u64 - 2000);
+		let call = Call::store { data: vec![0u8; MAX_DATA_SIZE as usize - 1999] };
+		assert_noop!(
+			TransactionStorage::pre_dispatch_signed(&who, &call),
+			InvalidTransaction::ExhaustsResources,
+		);
+		assert_noop!(
+			Into::<RuntimeCall>::into(call.clone()).dispatch(RuntimeOrigin::none()),
+			Error::TooManyBytes,
+		);
+		// Budget is reset in the next block
+		run_to_block(2, || None);
+		assert_eq!(TransactionStorage::block_bytes_remaining(), MAX_DATA_SIZE as u64);
+		assert_ok!(TransactionStorage::pre_dispatch_signed(&who, &call));
+	});
+}
+
+#[test]
+fn operational_data_bypasses_block_bytes() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		assert_ok!(TransactionStorage::store(
+			RuntimeOrigin::none(),
+			vec![0u8; MAX_DATA_SIZE as usize]
+		));
+		assert_eq!(TransactionStorage::block_bytes_remaining(), 0);
+
+		let data = vec![1u8; 2000];
+		let call = Call::store_operational { data: data.clone() };
+		assert_eq!(call.get_dispatch_info().class, DispatchClass::Operational);
+		// Preimage authorization is required, even for signed transactions
+		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), 1, 1, 2000));
+		assert_noop!(
+			TransactionStorage::pre_dispatch_signed(&1, &call),
+			InvalidTransaction::Payment,
+		);
+		assert_ok!(TransactionStorage::authorize_preimage(
+			RuntimeOrigin::root(),
+			blake2_256(&data),
+			2000
+		));
+		let validity =
+			TransactionStorage::validate_unsigned(TransactionSource::External, &call).unwrap();
+		assert_eq!(validity.priority, OperationalStorePriority::get());
+		assert_ok!(TransactionStorage::pre_dispatch(&call));
+		assert_ok!(Into::<RuntimeCall>::into(call).dispatch(RuntimeOrigin::none()));
+		assert_noop!(
+			TransactionStorage::store(RuntimeOrigin::none(), vec![2u8; 1]),
+			Error::TooManyBytes,
+		);
+	});
+}
+
+#[test]
+fn stores_in_namespace() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		let namespace: Namespace = b"people-identity".to_vec().try_into().unwrap();
+		let data = vec![0u8; 2000];
+		assert_noop!(
+			TransactionStorage::store_in_namespace(
+				RuntimeOrigin::none(),
+				namespace.clone(),
+				data.clone()
+			),
+			Error::BadNamespace,
+		);
+		let config =
+			NamespaceConfig { max_item_size: 2000, retention: 7, max_total_bytes: u64::MAX };
+		assert_noop!(
+			TransactionStorage::set_namespace_config(
+				RuntimeOrigin::signed(1),
+				namespace.clone(),
+				Some(config.clone())
+			),
+			DispatchError::BadOrigin,
+		);
+		assert_ok!(TransactionStorage::set_namespace_config(
+			RuntimeOrigin::root(),
+			namespace.clone(),
+			Some(config)
+		));
+		assert_noop!(
+			TransactionStorage::store_in_namespace(
+				RuntimeOrigin::none(),
+				namespace.clone(),
+				vec![0u8; 2001]
+			),
+			Error::BadNamespace,
+		);
+		assert_ok!(TransactionStorage::store_in_namespace(
+			RuntimeOrigin::none(),
+			namespace.clone(),
+			data.clone()
+		));
+		System::assert_last_event(RuntimeEvent::TransactionStorage(Event::Stored {
+			index: 0,
+			cid: super::cid(&blake2_256(&data)),
+			namespace: Some(namespace.clone()),
+		}));
+		let entry = ContentIndex::get(blake2_256(&data)).unwrap();
+		assert_eq!(entry.namespace, Some(namespace));
+
+		// Renewal is only possible within the namespace's retention period
+		run_to_block(5, || None);
+		assert_ok!(TransactionStorage::renew(RuntimeOrigin::none(), 1, 0));
+		run_to_block(8, || None);
+		let call = Call::renew { block: 5, index: 0 };
+		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), 1, 1, 2000));
+		assert_noop!(TransactionStorage::pre_dispatch_signed(&1, &call), RETENTION_EXCEEDED);
+		assert_noop!(
+			TransactionStorage::renew(RuntimeOrigin::none(), 5, 0),
+			Error::RetentionExceeded,
+		);
+	});
+}
+
+#[test]
+fn plain_store_keeps_namespace() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		let namespace: Namespace = b"test".to_vec().try_into().unwrap();
+		let config = NamespaceConfig { max_item_size: 2000, retention: 7, max_total_bytes: 4000 };
+		assert_ok!(TransactionStorage::set_namespace_config(
+			RuntimeOrigin::root(),
+			namespace.clone(),
+			Some(config)
+		));
+		let data = vec![0u8; 2000];
+		assert_ok!(TransactionStorage::store_in_namespace(
+			RuntimeOrigin::none(),
+			namespace.clone(),
+			data.clone()
+		));
+
+		// Storing the same data again without a namespace neither takes it out of the namespace
+		// nor restarts its retention
+		run_to_block(5, || None);
+		assert_ok!(TransactionStorage::store(RuntimeOrigin::none(), data.clone()));
+		let entry = ContentIndex::get(blake2_256(&data)).unwrap();
+		assert_eq!((entry.namespace, entry.stored_at, entry.refs), (Some(namespace), 1, 2));
+		run_to_block(8, || None);
+		assert_noop!(
+			TransactionStorage::renew(RuntimeOrigin::none(), 5, 0),
+			Error::RetentionExceeded,
+		);
+	});
+}
+
+#[test]
+fn validates_namespace_payloads() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		let config = NamespaceConfig { max_item_size: 2000, retention: 100, max_total_bytes: 4000 };
+		let wasm: Namespace = b"wasm".to_vec().try_into().unwrap();
+		let raw: Namespace = b"raw".to_vec().try_into().unwrap();
+		for namespace in [&wasm, &raw] {
+			assert_ok!(TransactionStorage::set_namespace_config(
+				RuntimeOrigin::root(),
+				namespace.clone(),
+				Some(config.clone())
+			));
+		}
+
+		// Structured namespaces reject garbage, raw namespaces accept anything
+		assert_noop!(
+			TransactionStorage::store_in_namespace(
+				RuntimeOrigin::none(),
+				wasm.clone(),
+				vec![1u8; 8]
+			),
+			Error::InvalidPayload,
+		);
+		assert_ok!(TransactionStorage::store_in_namespace(
+			RuntimeOrigin::none(),
+			wasm,
+			b"\0asm\x01\0\0\0".to_vec()
+		));
+		assert_ok!(TransactionStorage::store_in_namespace(
+			RuntimeOrigin::none(),
+			raw,
+			vec![1u8; 8]
+		));
+	});
+}
+
+#[test]
+fn limits_namespace_total_bytes() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		let namespace: Namespace = b"test".to_vec().try_into().unwrap();
+		let config = NamespaceConfig { max_item_size: 2000, retention: 100, max_total_bytes: 4000 };
+		assert_ok!(TransactionStorage::set_namespace_config(
+			RuntimeOrigin::root(),
+			namespace.clone(),
+			Some(config)
+		));
+		let data = vec![vec![1u8; 2000], vec![2u8; 2000]];
+		for d in &data {
+			assert_ok!(TransactionStorage::store_in_namespace(
+				RuntimeOrigin::none(),
+				namespace.clone(),
+				d.clone()
+			));
+		}
+		assert_eq!(NamespaceBytes::get(&namespace), 4000);
+
+		// Namespace is full
+		let call = Call::store_in_namespace { namespace: namespace.clone(), data: vec![3u8; 1] };
+		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), 1, 1, 2000));
+		assert_noop!(TransactionStorage::pre_dispatch_signed(&1, &call), BAD_NAMESPACE);
+		assert_noop!(
+			TransactionStorage::store_in_namespace(
+				RuntimeOrigin::none(),
+				namespace.clone(),
+				vec![3u8; 1]
+			),
+			Error::BadNamespace,
+		);
+		run_to_block(2, || None);
+		assert_noop!(TransactionStorage::renew(RuntimeOrigin::none(), 1, 0), Error::BadNamespace);
+
+		// Retracted data no longer counts, but renewals do
+		assert_ok!(TransactionStorage::retract(RuntimeOrigin::root(), 1, 1));
+		assert_eq!(NamespaceBytes::get(&namespace), 2000);
+		assert_ok!(TransactionStorage::renew(RuntimeOrigin::none(), 1, 0));
+		assert_eq!(NamespaceBytes::get(&namespace), 4000);
+
+		let proof_provider = || {
+			let block_num = System::block_number();
+			if block_num == 11 {
+				let parent_hash = System::parent_hash();
+				Some(build_proof(parent_hash.as_ref(), data.clone()).unwrap())
+			} else {
+				None
+			}
+		};
+		run_to_block(12, proof_provider);
+		assert_eq!(NamespaceBytes::get(&namespace), 2000);
+	});
+}
+
 #[test]
 fn uses_account_authorization() {
 	new_test_ext().execute_with(|| {
@@ -132,6 +505,35 @@ fn uses_preimage_authorization() {
 	});
 }
 
+#[test]
+fn signed_transactions_use_preimage_authorization() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		let who = 1;
+		let data = vec![2; 2000];
+		let hash = blake2_256(&data);
+		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), who, 2, 4000));
+		assert_ok!(TransactionStorage::authorize_preimage(RuntimeOrigin::root(), hash, 2000));
+		let call = Call::store { data };
+		// Any account may submit the preimage
+		assert_ok!(TransactionStorage::pre_dispatch_signed(&5, &call));
+		assert_eq!(
+			TransactionStorage::preimage_authorization_extent(hash),
+			AuthorizationExtent { transactions: 0, bytes: 0 }
+		);
+		assert_noop!(
+			TransactionStorage::pre_dispatch_signed(&5, &call),
+			InvalidTransaction::Payment,
+		);
+		// Once the preimage authorization is used up, account authorization is used
+		assert_ok!(TransactionStorage::pre_dispatch_signed(&who, &call));
+		assert_eq!(
+			TransactionStorage::account_authorization_extent(who),
+			AuthorizationExtent { transactions: 1, bytes: 2000 }
+		);
+	});
+}
+
 #[test]
 fn checks_proof() {
 	new_test_ext().execute_with(|| {
@@ -163,6 +565,29 @@ fn checks_proof() {
 	});
 }
 
+#[test]
+fn checks_proof_of_random_chunk() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		let data = vec![0u8; 2000];
+		assert_ok!(TransactionStorage::store(RuntimeOrigin::none(), data.clone()));
+		run_to_block(11, || None);
+
+		// The randomness selects chunk 0 rather than chunk 1
+		ProofRandomness::set(Some(H256::zero()));
+		assert_eq!(TransactionStorage::proof_randomness(), Some(H256::zero()));
+		let mut other_chunk = [0u8; 32];
+		other_chunk[7] = 1;
+		let invalid_proof = build_proof(&other_chunk, vec![data.clone()]).unwrap();
+		assert_noop!(
+			TransactionStorage::check_proof(RuntimeOrigin::none(), invalid_proof),
+			Error::InvalidProof,
+		);
+		let proof = build_proof(H256::zero().as_ref(), vec![data]).unwrap();
+		assert_ok!(TransactionStorage::check_proof(RuntimeOrigin::none(), proof));
+	});
+}
+
 #[test]
 fn renews_data() {
 	new_test_ext().execute_with(|| {
@@ -193,50 +618,311 @@ fn renews_data() {
 }
 
 #[test]
-fn authorization_expires() {
+fn exposes_transactions_key() {
 	new_test_ext().execute_with(|| {
 		run_to_block(1, || None);
-		let who = 1;
-		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), who, 1, 2000));
-		assert_eq!(
-			TransactionStorage::account_authorization_extent(who),
-			AuthorizationExtent { transactions: 1, bytes: 2000 },
-		);
-		let call = Call::store { data: vec![0; 2000] };
-		assert_ok!(TransactionStorage::validate_signed(&who, &call));
-		run_to_block(10, || None);
-		assert_eq!(
-			TransactionStorage::account_authorization_extent(who),
-			AuthorizationExtent { transactions: 1, bytes: 2000 },
-		);
-		assert_ok!(TransactionStorage::validate_signed(&who, &call));
-		run_to_block(11, || None);
-		assert_eq!(
-			TransactionStorage::account_authorization_extent(who),
-			AuthorizationExtent { transactions: 0, bytes: 0 },
-		);
-		assert_noop!(TransactionStorage::validate_signed(&who, &call), InvalidTransaction::Payment);
+		let data = vec![0u8; 2000];
+		assert_ok!(TransactionStorage::store(RuntimeOrigin::none(), data.clone()));
+		run_to_block(2, || None);
+
+		let key = TransactionStorage::transactions_key(1);
+		let infos: Vec<TransactionInfo> =
+			Decode::decode(&mut &sp_io::storage::get(&key).unwrap()[..]).unwrap();
+		assert_eq!(infos.len(), 1);
+		assert_eq!(infos[0].content_hash, blake2_256(&data).into());
+		assert_eq!(infos[0].size, 2000);
 	});
 }
 
 #[test]
-fn expired_authorization_clears() {
+fn indexes_content_location() {
 	new_test_ext().execute_with(|| {
 		run_to_block(1, || None);
-		let who = 1;
-		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), who, 2, 2000));
-		assert_eq!(
-			TransactionStorage::account_authorization_extent(who),
-			AuthorizationExtent { transactions: 2, bytes: 2000 },
-		);
+		let data = vec![0u8; 2000];
+		let hash = blake2_256(&data);
+		assert_eq!(TransactionStorage::content_location(hash), None);
+		assert_ok!(TransactionStorage::store(RuntimeOrigin::none(), vec![1u8; 2000]));
+		assert_ok!(TransactionStorage::store(RuntimeOrigin::none(), data.clone()));
+		assert_eq!(TransactionStorage::content_location(hash), Some((1, 1)));
+		let proof_provider = || {
+			let block_num = System::block_number();
+			if block_num == 11 {
+				let parent_hash = System::parent_hash();
+				Some(
+					build_proof(parent_hash.as_ref(), vec![vec![1u8; 2000], data.clone()]).unwrap(),
+				)
+			} else if block_num == 16 {
+				let parent_hash = System::parent_hash();
+				Some(build_proof(parent_hash.as_ref(), vec![data.clone()]).unwrap())
+			} else {
+				None
+			}
+		};
+		run_to_block(6, proof_provider);
+		assert_ok!(TransactionStorage::renew(RuntimeOrigin::none(), 1, 1));
+		assert_eq!(TransactionStorage::content_location(hash), Some((6, 0)));
+		// The original location expiring doesn't drop the renewed one
+		run_to_block(12, proof_provider);
+		assert_eq!(TransactionStorage::content_location(hash), Some((6, 0)));
+		assert_eq!(TransactionStorage::content_location(blake2_256(&[1u8; 2000])), None);
+		run_to_block(17, proof_provider);
+		assert_eq!(TransactionStorage::content_location(hash), None);
+	});
+}
 
-		// User uses some of the authorization, and the remaining amount gets updated appropriately
-		run_to_block(2, || None);
-		let store_call = Call::store { data: vec![0; 1000] };
-		assert_ok!(TransactionStorage::pre_dispatch_signed(&who, &store_call));
+#[test]
+fn deduplicates_content() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		let data = vec![0u8; 2000];
+		let hash = blake2_256(&data);
+		assert_ok!(TransactionStorage::store(RuntimeOrigin::none(), data.clone()));
+		assert_ok!(TransactionStorage::store(RuntimeOrigin::none(), data.clone()));
+		run_to_block(3, || None);
+		assert_ok!(TransactionStorage::store(RuntimeOrigin::none(), data.clone()));
+		assert_eq!(ContentIndex::get(hash).map(|entry| entry.refs), Some(3));
+		assert_eq!(TransactionStorage::content_location(hash), Some((3, 0)));
+		let proof_provider = || {
+			let block_num = System::block_number();
+			if block_num == 11 {
+				let parent_hash = System::parent_hash();
+				Some(build_proof(parent_hash.as_ref(), vec![data.clone(), data.clone()]).unwrap())
+			} else if block_num == 13 {
+				let parent_hash = System::parent_hash();
+				Some(build_proof(parent_hash.as_ref(), vec![data.clone()]).unwrap())
+			} else {
+				None
+			}
+		};
+		run_to_block(12, proof_provider);
+		assert_eq!(ContentIndex::get(hash).map(|entry| entry.refs), Some(1));
+		assert_eq!(TransactionStorage::content_location(hash), Some((3, 0)));
+		run_to_block(14, proof_provider);
+		assert!(!ContentIndex::contains_key(hash));
+	});
+}
+
+#[test]
+fn reports_data_status() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		let data = vec![0u8; 2000];
+		let hash = blake2_256(&data);
+		assert_eq!(TransactionStorage::data_status(hash), DataStatus::Unknown);
+		assert_ok!(TransactionStorage::store(RuntimeOrigin::none(), data.clone()));
+		assert_eq!(TransactionStorage::data_status(hash), DataStatus::Included(1));
+
+		let root = upload_root(&[vec![1u8; 2000], vec![2u8; 2000]]);
+		assert_ok!(TransactionStorage::begin_upload(RuntimeOrigin::signed(1), root, 4000, 2));
+		assert_ok!(TransactionStorage::upload_chunk(
+			RuntimeOrigin::signed(1),
+			0,
+			0,
+			vec![1u8; 2000]
+		));
+		assert_eq!(TransactionStorage::data_status(root), DataStatus::PendingChunks(1));
+		assert_ok!(TransactionStorage::upload_chunk(
+			RuntimeOrigin::signed(1),
+			0,
+			1,
+			vec![2u8; 2000]
+		));
+		assert_ok!(TransactionStorage::finalize_upload(RuntimeOrigin::signed(1), 0));
 		assert_eq!(
-			TransactionStorage::account_authorization_extent(who),
-			AuthorizationExtent { transactions: 1, bytes: 1000 },
+			TransactionStorage::data_status(root),
+			DataStatus::Uploaded(UploadReceipt {
+				root,
+				size: 4000,
+				chunk_count: 2,
+				first_block: 1,
+				last_block: 1,
+			}),
+		);
+
+		let proof_provider = || {
+			let block_num = System::block_number();
+			if block_num == 11 {
+				let parent_hash = System::parent_hash();
+				let transactions = vec![data.clone(), vec![1u8; 2000], vec![2u8; 2000]];
+				Some(build_proof(parent_hash.as_ref(), transactions).unwrap())
+			} else {
+				None
+			}
+		};
+		run_to_block(12, proof_provider);
+		assert_eq!(TransactionStorage::data_status(hash), DataStatus::Expired);
+		run_to_block(22, proof_provider);
+		assert_eq!(TransactionStorage::data_status(hash), DataStatus::Unknown);
+		assert!(ExpiredContent::contains_key(hash));
+		TransactionStorage::on_idle(22, Weight::MAX);
+		assert!(!ExpiredContent::contains_key(hash));
+	});
+}
+
+#[test]
+fn retracts_data() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		let data = vec![0u8; 2000];
+		let hash = blake2_256(&data);
+		assert_ok!(TransactionStorage::store(RuntimeOrigin::signed(1), data.clone()));
+		assert_ok!(TransactionStorage::store(RuntimeOrigin::none(), vec![1u8; 2000]));
+		run_to_block(2, || None);
+
+		assert_noop!(
+			TransactionStorage::retract(RuntimeOrigin::signed(2), 1, 0),
+			Error::NotUploader,
+		);
+		assert_noop!(
+			TransactionStorage::retract(RuntimeOrigin::signed(1), 1, 1),
+			Error::NotUploader,
+		);
+		assert_noop!(
+			TransactionStorage::pre_dispatch_signed(&2, &Call::retract { block: 1, index: 0 }),
+			InvalidTransaction::BadSigner,
+		);
+		assert_ok!(TransactionStorage::pre_dispatch_signed(
+			&1,
+			&Call::retract { block: 1, index: 0 }
+		));
+		assert_ok!(TransactionStorage::retract(RuntimeOrigin::signed(1), 1, 0));
+		System::assert_last_event(RuntimeEvent::TransactionStorage(Event::Retracted {
+			block: 1,
+			index: 0,
+			content_hash: hash,
+		}));
+		assert_eq!(TransactionStorage::data_status(hash), DataStatus::Unknown);
+		assert_noop!(
+			TransactionStorage::renew(RuntimeOrigin::none(), 1, 0),
+			Error::RenewedNotFound,
+		);
+		assert_noop!(
+			TransactionStorage::retract(RuntimeOrigin::signed(1), 1, 0),
+			Error::RetractedNotFound,
+		);
+
+		// Governance can retract anything
+		assert_ok!(TransactionStorage::retract(RuntimeOrigin::root(), 1, 1));
+
+		// Retracted data must still be proved until it expires
+		let proof_provider = || {
+			let block_num = System::block_number();
+			if block_num == 11 {
+				let parent_hash = System::parent_hash();
+				Some(
+					build_proof(parent_hash.as_ref(), vec![data.clone(), vec![1u8; 2000]]).unwrap(),
+				)
+			} else {
+				None
+			}
+		};
+		run_to_block(12, proof_provider);
+		assert!(!Retracted::contains_key(1, 0));
+		assert!(!Uploaders::contains_key(1, 0));
+	});
+}
+
+#[test]
+fn retracting_newest_copy_repoints_content_index() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		let data = vec![0u8; 2000];
+		let hash = blake2_256(&data);
+		assert_ok!(TransactionStorage::store(RuntimeOrigin::none(), data.clone()));
+		run_to_block(2, || None);
+		assert_ok!(TransactionStorage::renew(RuntimeOrigin::none(), 1, 0));
+		run_to_block(3, || None);
+		assert_ok!(TransactionStorage::renew(RuntimeOrigin::none(), 2, 0));
+		run_to_block(4, || None);
+		assert_eq!(TransactionStorage::content_location(hash), Some((3, 0)));
+
+		// Retracting the newest copy points the index at the newest remaining one
+		assert_ok!(TransactionStorage::retract(RuntimeOrigin::root(), 3, 0));
+		assert_eq!(TransactionStorage::content_location(hash), Some((2, 0)));
+		assert_eq!(ContentIndex::get(hash).unwrap().refs, 2);
+		assert_ok!(TransactionStorage::pin(RuntimeOrigin::signed(1), hash));
+		System::assert_last_event(RuntimeEvent::TransactionStorage(Event::Renewed { index: 0 }));
+		assert_eq!(TransactionStorage::content_location(hash), Some((4, 0)));
+
+		// Retracting an older copy leaves the index alone
+		assert_ok!(TransactionStorage::retract(RuntimeOrigin::root(), 2, 0));
+		assert_eq!(TransactionStorage::content_location(hash), Some((4, 0)));
+		assert_ok!(TransactionStorage::retract(RuntimeOrigin::root(), 4, 0));
+		assert_eq!(TransactionStorage::content_location(hash), Some((1, 0)));
+
+		// Retracting the last copy removes the entry
+		assert_ok!(TransactionStorage::retract(RuntimeOrigin::root(), 1, 0));
+		assert_eq!(TransactionStorage::content_location(hash), None);
+		assert!(!ContentIndex::contains_key(hash));
+		assert_eq!(ContentCopies::iter_prefix(hash).count(), 0);
+	});
+}
+
+#[test]
+fn authorization_expires() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		let who = 1;
+		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), who, 1, 2000));
+		assert_eq!(
+			TransactionStorage::account_authorization_extent(who),
+			AuthorizationExtent { transactions: 1, bytes: 2000 },
+		);
+		let call = Call::store { data: vec![0; 2000] };
+		assert_ok!(TransactionStorage::validate_signed(&who, &call));
+		run_to_block(10, || None);
+		assert_eq!(
+			TransactionStorage::account_authorization_extent(who),
+			AuthorizationExtent { transactions: 1, bytes: 2000 },
+		);
+		assert_ok!(TransactionStorage::validate_signed(&who, &call));
+		run_to_block(11, || None);
+		assert_eq!(
+			TransactionStorage::account_authorization_extent(who),
+			AuthorizationExtent { transactions: 0, bytes: 0 },
+		);
+		assert_noop!(TransactionStorage::validate_signed(&who, &call), InvalidTransaction::Payment);
+	});
+}
+
+#[test]
+fn reports_authorization_status() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		let who = 1;
+		assert_eq!(TransactionStorage::account_authorization_status(who), None);
+		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), who, 2, 2000));
+		assert_ok!(TransactionStorage::pre_dispatch_signed(
+			&who,
+			&Call::store { data: vec![0; 500] }
+		));
+		assert_eq!(
+			TransactionStorage::account_authorization_status(who),
+			Some(AuthorizationStatus { bytes_left: 1500, transactions_left: 1, expires_at: 11 })
+		);
+		run_to_block(11, || None);
+		assert_eq!(TransactionStorage::account_authorization_status(who), None);
+	});
+}
+
+#[test]
+fn expired_authorization_clears() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		let who = 1;
+		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), who, 2, 2000));
+		assert_eq!(
+			TransactionStorage::account_authorization_extent(who),
+			AuthorizationExtent { transactions: 2, bytes: 2000 },
+		);
+
+		// User uses some of the authorization, and the remaining amount gets updated appropriately
+		run_to_block(2, || None);
+		let store_call = Call::store { data: vec![0; 1000] };
+		assert_ok!(TransactionStorage::pre_dispatch_signed(&who, &store_call));
+		assert_eq!(
+			TransactionStorage::account_authorization_extent(who),
+			AuthorizationExtent { transactions: 1, bytes: 1000 },
 		);
 
 		// Can't remove too early
@@ -267,6 +953,67 @@ fn expired_authorization_clears() {
 	});
 }
 
+#[test]
+fn expired_authorizations_removed_on_idle() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		let hash = blake2_256(&[1]);
+		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), 1, 1, 2000));
+		assert_ok!(TransactionStorage::authorize_preimage(RuntimeOrigin::root(), hash, 2000));
+		run_to_block(5, || None);
+		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), 2, 1, 2000));
+		run_to_block(11, || None);
+
+		// No weight, nothing removed
+		TransactionStorage::on_idle(11, Weight::zero());
+		assert_eq!(Authorizations::iter().count(), 3);
+
+		TransactionStorage::on_idle(11, Weight::MAX);
+		System::assert_has_event(RuntimeEvent::TransactionStorage(
+			Event::ExpiredAccountAuthorizationRemoved { who: 1 },
+		));
+		System::assert_has_event(RuntimeEvent::TransactionStorage(
+			Event::ExpiredPreimageAuthorizationRemoved { hash },
+		));
+		assert!(!Authorizations::contains_key(AuthorizationScope::Account(1)));
+		assert!(!Authorizations::contains_key(AuthorizationScope::Preimage(hash)));
+		// Not yet expired
+		assert!(Authorizations::contains_key(AuthorizationScope::Account(2)));
+	});
+}
+
+#[test]
+fn account_authorizations_provide_account_references() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		assert_eq!(System::providers(&1), 0);
+		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), 1, 1, 2000));
+		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), 1, 1, 2000));
+		assert_eq!(System::providers(&1), 1);
+
+		// Used up, but kept until it expires
+		let call = Call::store { data: vec![0; 1000] };
+		assert_ok!(TransactionStorage::pre_dispatch_signed(&1, &call));
+		assert_eq!(System::providers(&1), 1);
+		assert_ok!(TransactionStorage::pre_dispatch_signed(&1, &call));
+		assert_eq!(System::providers(&1), 1);
+
+		// Expired and removed
+		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), 1, 1, 2000));
+		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), 2, 1, 2000));
+		assert_eq!(System::providers(&1), 1);
+		assert_eq!(System::providers(&2), 1);
+		run_to_block(11, || None);
+		assert_ok!(TransactionStorage::remove_expired_account_authorization(
+			RuntimeOrigin::none(),
+			1
+		));
+		assert_eq!(System::providers(&1), 0);
+		TransactionStorage::on_idle(11, Weight::MAX);
+		assert_eq!(System::providers(&2), 0);
+	});
+}
+
 #[test]
 fn consumed_authorization_clears() {
 	new_test_ext().execute_with(|| {
@@ -288,7 +1035,1122 @@ fn consumed_authorization_clears() {
 		);
 		// Consume the remaining amount
 		assert_ok!(TransactionStorage::pre_dispatch_signed(&who, &call));
-		// Key should be cleared from Authorizations
-		assert!(!Authorizations::contains_key(AuthorizationScope::Account(who)));
+		assert_eq!(
+			TransactionStorage::account_authorization_extent(who),
+			AuthorizationExtent { transactions: 0, bytes: 0 },
+		);
+		assert_noop!(
+			TransactionStorage::pre_dispatch_signed(&who, &call),
+			InvalidTransaction::Payment,
+		);
+		// Key is kept until the authorization expires
+		assert!(Authorizations::contains_key(AuthorizationScope::Account(who)));
+	});
+}
+
+#[test]
+fn consuming_last_authorization_keeps_nonce() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), 1, 1, 1000));
+		// As done by CheckNonce before pre_dispatch_signed
+		System::inc_account_nonce(1);
+		let call = Call::store { data: vec![0; 1000] };
+		assert_ok!(TransactionStorage::pre_dispatch_signed(&1, &call));
+		// The account is not reaped, so the transaction can't be replayed once the account is
+		// authorized again
+		assert_eq!(System::account_nonce(1), 1);
+		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), 1, 1, 1000));
+		assert_eq!(System::account_nonce(1), 1);
+	});
+}
+
+#[test]
+fn stores_attested_data() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		let data = vec![0u8; 2000];
+		let pair = sr25519::Pair::from_seed(&[1; 32]);
+		let mut attestation = Attestation {
+			content_type: b"application/json".to_vec().try_into().unwrap(),
+			schema_version: 2,
+			signer: pair.public().into(),
+			signature: pair.sign(b"not the message").into(),
+		};
+		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), 1, 2, 4000));
+		let call =
+			Call::store_with_attestation { attestation: attestation.clone(), data: data.clone() };
+		assert_noop!(TransactionStorage::pre_dispatch_signed(&1, &call), BAD_ATTESTATION);
+		assert_noop!(
+			TransactionStorage::store_with_attestation(
+				RuntimeOrigin::signed(1),
+				attestation.clone(),
+				data.clone()
+			),
+			Error::BadAttestation,
+		);
+
+		attestation.signature = pair.sign(&attestation.message(&blake2_256(&data))).into();
+		let call =
+			Call::store_with_attestation { attestation: attestation.clone(), data: data.clone() };
+		assert_ok!(TransactionStorage::pre_dispatch_signed(&1, &call));
+		assert_ok!(call.dispatch(RuntimeOrigin::signed(1)));
+		System::assert_last_event(RuntimeEvent::TransactionStorage(Event::Attested {
+			index: 0,
+			attestation: attestation.clone(),
+		}));
+		run_to_block(2, || None);
+		assert_eq!(Attestations::get(1, 0), Some(attestation));
+
+		let proof_provider = || {
+			let block_num = System::block_number();
+			if block_num == 11 {
+				let parent_hash = System::parent_hash();
+				Some(build_proof(parent_hash.as_ref(), vec![data.clone()]).unwrap())
+			} else {
+				None
+			}
+		};
+		run_to_block(12, proof_provider);
+		assert_eq!(Attestations::get(1, 0), None);
+	});
+}
+
+/// A Zstandard frame of a single RLE block of `size` bytes.
+fn zstd_rle_frame(size: u32) -> Vec<u8> {
+	let mut frame = vec![0x28, 0xb5, 0x2f, 0xfd, 0xe0];
+	frame.extend_from_slice(&u64::from(size).to_le_bytes());
+	frame.extend_from_slice(&((size << 3) | (1 << 1) | 1).to_le_bytes()[..3]);
+	frame.push(0);
+	frame
+}
+
+#[test]
+fn charges_decoded_size_of_encoded_data() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), 1, 3, 4000));
+
+		let call =
+			Call::store_encoded { encoding: ContentEncoding::Zstd, data: zstd_rle_frame(3000) };
+		assert_ok!(TransactionStorage::pre_dispatch_signed(&1, &call));
+		assert_ok!(call.dispatch(RuntimeOrigin::signed(1)));
+		System::assert_last_event(RuntimeEvent::TransactionStorage(Event::Encoded {
+			index: 0,
+			encoding: ContentEncoding::Zstd,
+			decoded_size: 3000,
+		}));
+		assert_eq!(
+			TransactionStorage::account_authorization_extent(1),
+			AuthorizationExtent { transactions: 2, bytes: 1000 },
+		);
+		assert_noop!(
+			TransactionStorage::pre_dispatch_signed(&1, &call),
+			InvalidTransaction::Payment,
+		);
+
+		// Data following the frame
+		let mut data = zstd_rle_frame(10);
+		data.push(0);
+		let call = Call::store_encoded { encoding: ContentEncoding::Zstd, data: data.clone() };
+		assert_noop!(TransactionStorage::pre_dispatch_signed(&1, &call), BAD_ENCODING);
+		assert_noop!(
+			TransactionStorage::store_encoded(
+				RuntimeOrigin::signed(1),
+				ContentEncoding::Zstd,
+				data
+			),
+			Error::BadEncoding,
+		);
+		let call = Call::store_encoded { encoding: ContentEncoding::Zstd, data: vec![0; 20] };
+		assert_noop!(TransactionStorage::pre_dispatch_signed(&1, &call), BAD_ENCODING);
+
+		// Unencoded data is charged its size
+		let call = Call::store_encoded { encoding: ContentEncoding::Identity, data: vec![1; 20] };
+		assert_ok!(TransactionStorage::pre_dispatch_signed(&1, &call));
+		assert_ok!(call.dispatch(RuntimeOrigin::signed(1)));
+		System::assert_last_event(RuntimeEvent::TransactionStorage(Event::Encoded {
+			index: 1,
+			encoding: ContentEncoding::Identity,
+			decoded_size: 20,
+		}));
+		assert_eq!(
+			TransactionStorage::account_authorization_extent(1),
+			AuthorizationExtent { transactions: 1, bytes: 980 },
+		);
+	});
+}
+
+#[test]
+fn names_point_to_stored_data() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		let first = blake2_256(&[1]);
+		let second = blake2_256(&[2]);
+		assert_ok!(TransactionStorage::store(RuntimeOrigin::none(), vec![1]));
+		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), 1, 2, 2000));
+		let name: Name = b"latest-runtime".to_vec().try_into().unwrap();
+
+		let call = Call::claim_name { name: name.clone(), content_hash: second };
+		assert_noop!(TransactionStorage::pre_dispatch_signed(&1, &call), BAD_NAME);
+		let call = Call::claim_name { name: name.clone(), content_hash: first };
+		assert_ok!(TransactionStorage::pre_dispatch_signed(&1, &call));
+		assert_ok!(call.dispatch(RuntimeOrigin::signed(1)));
+		System::assert_last_event(RuntimeEvent::TransactionStorage(Event::NameClaimed {
+			name: name.clone(),
+			owner: 1,
+			content_hash: first,
+		}));
+		assert_eq!(
+			TransactionStorage::account_authorization_extent(1),
+			AuthorizationExtent { transactions: 1, bytes: 2000 - name.len() as u64 },
+		);
+		assert_eq!(TransactionStorage::resolve_name(b"latest-runtime"), Some(first));
+		assert_noop!(
+			TransactionStorage::claim_name(RuntimeOrigin::signed(2), name.clone(), first),
+			Error::NameTaken,
+		);
+
+		// Only the owner may repoint, and only to data being kept
+		assert_ok!(TransactionStorage::store(RuntimeOrigin::none(), vec![2]));
+		let call = Call::point_name { name: name.clone(), content_hash: second };
+		assert_noop!(TransactionStorage::pre_dispatch_signed(&2, &call), BAD_NAME);
+		assert_noop!(
+			TransactionStorage::point_name(RuntimeOrigin::signed(1), name.clone(), [0; 32]),
+			Error::ContentNotFound,
+		);
+		assert_ok!(TransactionStorage::pre_dispatch_signed(&1, &call));
+		assert_ok!(call.dispatch(RuntimeOrigin::signed(1)));
+		System::assert_last_event(RuntimeEvent::TransactionStorage(Event::NamePointed {
+			name: name.clone(),
+			content_hash: second,
+			previous: first,
+		}));
+		assert_eq!(TransactionStorage::resolve_name(b"latest-runtime"), Some(second));
+
+		assert_noop!(
+			TransactionStorage::release_name(RuntimeOrigin::signed(2), name.clone()),
+			Error::NotNameOwner,
+		);
+		assert_ok!(TransactionStorage::release_name(RuntimeOrigin::signed(1), name.clone()));
+		System::assert_last_event(RuntimeEvent::TransactionStorage(Event::NameReleased { name }));
+		assert_eq!(TransactionStorage::resolve_name(b"latest-runtime"), None);
+	});
+}
+
+#[test]
+fn pins_data_by_hash() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		let data = vec![0u8; 1000];
+		let hash = blake2_256(&data);
+		assert_ok!(TransactionStorage::store(RuntimeOrigin::none(), data));
+		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), 1, 2, 3000));
+		run_to_block(2, || None);
+
+		let call = Call::pin { content_hash: [0; 32] };
+		assert_noop!(TransactionStorage::pre_dispatch_signed(&1, &call), RENEWED_NOT_FOUND);
+		let call = Call::pin { content_hash: hash };
+		assert_noop!(
+			TransactionStorage::pre_dispatch_signed(&2, &call),
+			InvalidTransaction::Payment,
+		);
+
+		assert_ok!(TransactionStorage::pre_dispatch_signed(&1, &call));
+		assert_eq!(
+			TransactionStorage::account_authorization_extent(1),
+			AuthorizationExtent { transactions: 1, bytes: 2000 },
+		);
+		assert_ok!(call.dispatch(RuntimeOrigin::signed(1)));
+		System::assert_last_event(RuntimeEvent::TransactionStorage(Event::Renewed { index: 0 }));
+		// Each pin uses the authorization, so it can't be used to validate unlimited pins
+		assert_ok!(TransactionStorage::pre_dispatch_signed(&1, &call));
+		assert_noop!(
+			TransactionStorage::pre_dispatch_signed(&1, &call),
+			InvalidTransaction::Payment,
+		);
+		run_to_block(3, || None);
+		assert_eq!(TransactionStorage::content_location(hash), Some((2, 0)));
+	});
+}
+
+#[test]
+fn reveals_committed_data() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), 1, 3, 3000));
+		let data = vec![1u8; 1000];
+		let hash = blake2_256(&data);
+		let call = Call::commit { content_hash: hash };
+		assert_ok!(TransactionStorage::pre_dispatch_signed(&1, &call));
+		assert_ok!(call.dispatch(RuntimeOrigin::signed(1)));
+		System::assert_last_event(RuntimeEvent::TransactionStorage(Event::Committed {
+			owner: 1,
+			content_hash: hash,
+		}));
+		assert_eq!(System::providers(&1), 2);
+		assert_noop!(TransactionStorage::pre_dispatch_signed(&1, &call), BAD_COMMITMENT);
+		assert_noop!(
+			TransactionStorage::commit(RuntimeOrigin::signed(1), hash),
+			Error::AlreadyCommitted,
+		);
+
+		// Other accounts committing to the same data don't lock out the first committer
+		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), 2, 2, 64));
+		assert_ok!(TransactionStorage::pre_dispatch_signed(&2, &call));
+		assert_ok!(call.dispatch(RuntimeOrigin::signed(2)));
+
+		// Only committers can reveal
+		run_to_block(3, || None);
+		let call = Call::reveal { data: data.clone() };
+		assert_noop!(TransactionStorage::pre_dispatch_signed(&3, &call), BAD_COMMITMENT);
+		assert_noop!(
+			TransactionStorage::reveal(RuntimeOrigin::signed(3), data.clone()),
+			Error::CommitmentNotFound,
+		);
+		assert_ok!(TransactionStorage::pre_dispatch_signed(&1, &call));
+		assert_ok!(call.dispatch(RuntimeOrigin::signed(1)));
+		System::assert_last_event(RuntimeEvent::TransactionStorage(Event::Revealed {
+			index: 0,
+			committed: 1,
+		}));
+		assert_eq!(TransactionStorage::commit_block(hash), Some(1));
+		assert_eq!(System::providers(&1), 1);
+
+		// Published data can't be committed to
+		let call = Call::commit { content_hash: hash };
+		assert_noop!(TransactionStorage::pre_dispatch_signed(&1, &call), BAD_COMMITMENT);
+
+		// Commitments must be revealed within RevealPeriod blocks
+		let data = vec![2u8; 1000];
+		assert_ok!(TransactionStorage::commit(RuntimeOrigin::signed(1), blake2_256(&data)));
+		run_to_block(9, || None);
+		assert_noop!(
+			TransactionStorage::reveal(RuntimeOrigin::signed(1), data.clone()),
+			Error::CommitmentNotFound,
+		);
+		TransactionStorage::on_idle(9, Weight::MAX);
+		System::assert_has_event(RuntimeEvent::TransactionStorage(Event::CommitmentExpired {
+			owner: 1,
+			content_hash: blake2_256(&data),
+		}));
+		System::assert_has_event(RuntimeEvent::TransactionStorage(Event::CommitmentExpired {
+			owner: 2,
+			content_hash: hash,
+		}));
+		assert_eq!(System::providers(&2), 1);
+
+		// Publication block is dropped with the data
+		assert_ok!(TransactionStorage::retract(RuntimeOrigin::root(), 3, 0));
+		assert_eq!(TransactionStorage::commit_block(hash), None);
+	});
+}
+
+#[test]
+fn tracks_storage_usage() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		assert_noop!(
+			TransactionStorage::set_usage_threshold(RuntimeOrigin::signed(1), Some(3000)),
+			DispatchError::BadOrigin,
+		);
+		assert_ok!(TransactionStorage::set_usage_threshold(RuntimeOrigin::root(), Some(3000)));
+		System::assert_last_event(RuntimeEvent::TransactionStorage(Event::UsageThresholdSet {
+			threshold: Some(3000),
+		}));
+		let data = vec![0u8; 2000];
+		assert_ok!(TransactionStorage::store(RuntimeOrigin::signed(1), data.clone()));
+		assert_ok!(TransactionStorage::store(RuntimeOrigin::none(), vec![1u8; 2000]));
+		run_to_block(2, || None);
+
+		// Expires in block 12, which is in the usage period starting at block 10
+		System::assert_has_event(RuntimeEvent::TransactionStorage(Event::UsageThresholdCrossed {
+			total_bytes: 4000,
+			exceeded: true,
+		}));
+		assert_eq!(
+			TransactionStorage::storage_usage(),
+			StorageUsage {
+				total_bytes: 4000,
+				expiring_bytes: vec![(10, 4000)],
+				namespaces: vec![]
+			},
+		);
+
+		assert_ok!(TransactionStorage::retract(RuntimeOrigin::signed(1), 1, 0));
+		System::assert_last_event(RuntimeEvent::TransactionStorage(Event::UsageThresholdCrossed {
+			total_bytes: 2000,
+			exceeded: false,
+		}));
+		assert_eq!(TransactionStorage::storage_usage().expiring_bytes, vec![(10, 2000)]);
+
+		let proof_provider = || {
+			let block_num = System::block_number();
+			if block_num == 11 {
+				let parent_hash = System::parent_hash();
+				Some(
+					build_proof(parent_hash.as_ref(), vec![data.clone(), vec![1u8; 2000]]).unwrap(),
+				)
+			} else {
+				None
+			}
+		};
+		run_to_block(12, proof_provider);
+		assert_eq!(
+			TransactionStorage::storage_usage(),
+			StorageUsage { total_bytes: 0, expiring_bytes: vec![], namespaces: vec![] },
+		);
+	});
+}
+
+#[test]
+fn lists_expiring_data() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		let namespace: Namespace = b"test".to_vec().try_into().unwrap();
+		let config = NamespaceConfig { max_item_size: 2000, retention: 100, max_total_bytes: 4000 };
+		assert_ok!(TransactionStorage::set_namespace_config(
+			RuntimeOrigin::root(),
+			namespace.clone(),
+			Some(config)
+		));
+		let hashes: Vec<_> = (0..4u8).map(|i| blake2_256(&[i; 2000])).collect();
+		assert_ok!(TransactionStorage::store_in_namespace(
+			RuntimeOrigin::signed(1),
+			namespace.clone(),
+			vec![0u8; 2000]
+		));
+		assert_ok!(TransactionStorage::store(RuntimeOrigin::none(), vec![1u8; 2000]));
+		assert_ok!(TransactionStorage::store(RuntimeOrigin::signed(1), vec![2u8; 2000]));
+		run_to_block(2, || None);
+		assert_ok!(TransactionStorage::store(RuntimeOrigin::signed(1), vec![3u8; 2000]));
+		assert_ok!(TransactionStorage::renew(RuntimeOrigin::signed(2), 1, 2));
+		run_to_block(3, || None);
+
+		// Data stored in block 1 expires in block 12
+		assert_eq!(TransactionStorage::expiring_within(8, 0, 10), vec![]);
+		assert_eq!(
+			TransactionStorage::expiring_within(9, 0, 10),
+			vec![(hashes[0], Some(namespace.clone()), 12), (hashes[1], None, 12)],
+		);
+		assert_eq!(
+			TransactionStorage::expiring_within(10, 1, 2),
+			vec![(hashes[1], None, 12), (hashes[3], None, 13)],
+		);
+
+		// Renewed data is only listed under its latest expiry
+		assert_eq!(
+			TransactionStorage::expiring_within(100, 0, 10),
+			vec![
+				(hashes[0], Some(namespace), 12),
+				(hashes[1], None, 12),
+				(hashes[3], None, 13),
+				(hashes[2], None, 13),
+			],
+		);
+	});
+}
+
+#[test]
+fn transfers_account_authorization() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), 1, 3, 3000));
+		let call = Call::transfer_quota { dest: 2, transactions: 4, bytes: 1000 };
+		assert_noop!(
+			TransactionStorage::pre_dispatch_signed(&1, &call),
+			InvalidTransaction::Payment,
+		);
+		assert_noop!(
+			TransactionStorage::transfer_quota(RuntimeOrigin::signed(1), 2, 4, 1000),
+			Error::InsufficientAuthorization,
+		);
+		assert_noop!(
+			TransactionStorage::transfer_quota(RuntimeOrigin::signed(3), 2, 1, 1000),
+			Error::AuthorizationNotFound,
+		);
+
+		// New authorization for 2 expires along with the authorization of 1
+		let call = Call::transfer_quota { dest: 2, transactions: 1, bytes: 1000 };
+		assert_ok!(TransactionStorage::pre_dispatch_signed(&1, &call));
+		assert_ok!(call.dispatch(RuntimeOrigin::signed(1)));
+		System::assert_last_event(RuntimeEvent::TransactionStorage(
+			Event::AuthorizationTransferred { from: 1, to: 2, transactions: 1, bytes: 1000 },
+		));
+		assert_eq!(
+			TransactionStorage::account_authorization_extent(1),
+			AuthorizationExtent { transactions: 2, bytes: 2000 },
+		);
+		assert_eq!(
+			TransactionStorage::account_authorization_extent(2),
+			AuthorizationExtent { transactions: 1, bytes: 1000 },
+		);
+		assert_eq!(
+			Authorizations::get(AuthorizationScope::Account(2)).unwrap().expiration,
+			Authorizations::get(AuthorizationScope::Account(1)).unwrap().expiration,
+		);
+		assert_eq!(System::providers(&2), 1);
+
+		// Transferring everything uses up the authorization of 1, which is kept until it expires
+		assert_ok!(TransactionStorage::transfer_quota(RuntimeOrigin::signed(1), 2, 2, 2000));
+		assert_eq!(
+			TransactionStorage::account_authorization_extent(1),
+			AuthorizationExtent { transactions: 0, bytes: 0 },
+		);
+		assert_eq!(System::providers(&1), 1);
+		assert_eq!(
+			TransactionStorage::account_authorization_extent(2),
+			AuthorizationExtent { transactions: 3, bytes: 3000 },
+		);
+	});
+}
+
+#[test]
+fn delegates_account_authorization() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		assert_noop!(
+			TransactionStorage::delegate(RuntimeOrigin::signed(1), 2, 2000, 5),
+			Error::AuthorizationNotFound,
+		);
+		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), 1, 3, 3000));
+		assert_noop!(
+			TransactionStorage::delegate(RuntimeOrigin::signed(1), 1, 2000, 5),
+			Error::BadDelegation,
+		);
+		assert_noop!(
+			TransactionStorage::delegate(RuntimeOrigin::signed(1), 2, 2000, 1),
+			Error::BadDelegation,
+		);
+		let call = Call::delegate { who: 2, bytes_limit: 1500, expiration: 5 };
+		assert_ok!(TransactionStorage::pre_dispatch_signed(&1, &call));
+		assert_ok!(call.dispatch(RuntimeOrigin::signed(1)));
+		System::assert_last_event(RuntimeEvent::TransactionStorage(Event::Delegated {
+			owner: 1,
+			delegate: 2,
+			bytes_limit: 1500,
+			expiration: 5,
+		}));
+		assert_eq!(System::providers(&2), 1);
+
+		// Only one delegation per delegate
+		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), 3, 1, 1000));
+		assert_noop!(
+			TransactionStorage::delegate(RuntimeOrigin::signed(3), 2, 2000, 5),
+			Error::AlreadyDelegated,
+		);
+
+		// 2 stores against the authorization of 1, up to the delegated limit
+		let call = Call::store { data: vec![0; 1000] };
+		assert_ok!(TransactionStorage::pre_dispatch_signed(&2, &call));
+		assert_eq!(
+			TransactionStorage::account_authorization_extent(1),
+			AuthorizationExtent { transactions: 2, bytes: 2000 },
+		);
+		assert_noop!(
+			TransactionStorage::pre_dispatch_signed(&2, &call),
+			InvalidTransaction::Payment,
+		);
+
+		// 2's own authorization is preferred
+		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), 2, 2, 2000));
+		assert_ok!(TransactionStorage::pre_dispatch_signed(&2, &call));
+		assert_eq!(
+			TransactionStorage::account_authorization_extent(1),
+			AuthorizationExtent { transactions: 2, bytes: 2000 },
+		);
+		assert_eq!(
+			TransactionStorage::account_authorization_extent(2),
+			AuthorizationExtent { transactions: 1, bytes: 1000 },
+		);
+
+		// Only the accounts involved may revoke
+		let call = Call::revoke_delegation { who: 2 };
+		assert_noop!(TransactionStorage::pre_dispatch_signed(&3, &call), DELEGATION_NOT_FOUND);
+		assert_ok!(TransactionStorage::pre_dispatch_signed(&2, &call));
+		assert_ok!(call.dispatch(RuntimeOrigin::signed(2)));
+		System::assert_last_event(RuntimeEvent::TransactionStorage(Event::DelegationRevoked {
+			owner: 1,
+			delegate: 2,
+		}));
+		assert_eq!(System::providers(&2), 1);
+		assert_noop!(
+			TransactionStorage::revoke_delegation(RuntimeOrigin::signed(1), 2),
+			Error::DelegationNotFound,
+		);
+	});
+}
+
+#[test]
+fn removes_expired_delegations() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), 1, 3, 3000));
+		assert_ok!(TransactionStorage::delegate(RuntimeOrigin::signed(1), 2, 1000, 3));
+		assert_ok!(TransactionStorage::delegate(RuntimeOrigin::signed(1), 3, 1000, 5));
+		assert_eq!(System::providers(&2), 1);
+		assert_eq!(System::providers(&3), 1);
+
+		run_to_block(3, || None);
+		TransactionStorage::on_idle(3, Weight::MAX);
+		System::assert_has_event(RuntimeEvent::TransactionStorage(Event::DelegationExpired {
+			owner: 1,
+			delegate: 2,
+		}));
+		assert!(!Delegations::contains_key(2));
+		assert_eq!(System::providers(&2), 0);
+		// Not yet expired
+		assert!(Delegations::contains_key(3));
+		assert_eq!(System::providers(&3), 1);
+	});
+}
+
+#[test]
+fn force_sets_authorizations() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), 1, 3, 3000));
+		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), 2, 3, 3000));
+		let details =
+			|transactions, bytes| AuthorizationDetails { transactions, bytes, expiration: 20 };
+		let authorizations: BoundedVec<_, _> =
+			vec![(1, details(1, 1000)), (2, details(0, 0)), (3, details(2, 2000))]
+				.try_into()
+				.unwrap();
+		assert_noop!(
+			TransactionStorage::force_set_authorizations(
+				RuntimeOrigin::signed(1),
+				authorizations.clone()
+			),
+			DispatchError::BadOrigin,
+		);
+		assert_ok!(TransactionStorage::force_set_authorizations(
+			RuntimeOrigin::root(),
+			authorizations
+		));
+		System::assert_last_event(RuntimeEvent::TransactionStorage(
+			Event::AuthorizationsForceSet { count: 3 },
+		));
+
+		// Existing authorizations are replaced or used up
+		assert_eq!(
+			TransactionStorage::account_authorization_status(1),
+			Some(AuthorizationStatus { bytes_left: 1000, transactions_left: 1, expires_at: 20 }),
+		);
+		assert_eq!(
+			TransactionStorage::account_authorization_status(2),
+			Some(AuthorizationStatus { bytes_left: 0, transactions_left: 0, expires_at: 20 }),
+		);
+		assert_eq!(System::providers(&2), 1);
+		assert_eq!(
+			TransactionStorage::account_authorization_status(3),
+			Some(AuthorizationStatus { bytes_left: 2000, transactions_left: 2, expires_at: 20 }),
+		);
+		assert_eq!(System::providers(&3), 1);
+
+		// Used up authorizations are removed when they expire
+		run_to_block(20, || None);
+		TransactionStorage::on_idle(20, Weight::MAX);
+		assert!(!Authorizations::contains_key(AuthorizationScope::Account(2)));
+		assert_eq!(System::providers(&2), 0);
+	});
+}
+
+#[test]
+fn force_set_used_up_authorization_keeps_nonce() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), 1, 1, 1000));
+		// As done by CheckNonce before pre_dispatch_signed
+		System::inc_account_nonce(1);
+		let call = Call::store { data: vec![0; 1000] };
+		assert_ok!(TransactionStorage::pre_dispatch_signed(&1, &call));
+
+		let authorizations: BoundedVec<_, _> =
+			vec![(1, AuthorizationDetails { transactions: 0, bytes: 0, expiration: 11 })]
+				.try_into()
+				.unwrap();
+		assert_ok!(TransactionStorage::force_set_authorizations(
+			RuntimeOrigin::root(),
+			authorizations
+		));
+		assert_eq!(System::providers(&1), 1);
+		assert_eq!(System::account_nonce(1), 1);
+	});
+}
+
+#[test]
+fn replacing_expired_authorization_clears_rate_limit() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		let limit = Some(RateLimit { bytes: 1000, period: 100 });
+		let call = Call::store { data: vec![0u8; 2000] };
+		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), 1, 2, 2000));
+		assert_ok!(TransactionStorage::set_authorization_rate(RuntimeOrigin::root(), 1, limit));
+
+		// The expired authorization has not been removed, but the limit doesn't carry over to
+		// the new one
+		run_to_block(11, || None);
+		assert!(AuthorizationRates::contains_key(1));
+		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), 1, 2, 4000));
+		assert!(!AuthorizationRates::contains_key(1));
+		assert_ok!(TransactionStorage::pre_dispatch_signed(&1, &call));
+
+		// Nor to authorization transferred to the account
+		assert_ok!(TransactionStorage::set_authorization_rate(RuntimeOrigin::root(), 1, limit));
+		run_to_block(15, || None);
+		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), 2, 2, 4000));
+		run_to_block(21, || None);
+		assert_ok!(TransactionStorage::transfer_quota(RuntimeOrigin::signed(2), 1, 1, 2000));
+		assert!(!AuthorizationRates::contains_key(1));
+		assert_ok!(TransactionStorage::pre_dispatch_signed(&1, &call));
+	});
+}
+
+#[test]
+fn rate_limits_account_authorization() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), 1, 10, 10000));
+		let limit = RateLimit { bytes: 3000, period: 2 };
+		assert_noop!(
+			TransactionStorage::set_authorization_rate(RuntimeOrigin::signed(1), 1, Some(limit)),
+			DispatchError::BadOrigin,
+		);
+		assert_noop!(
+			TransactionStorage::set_authorization_rate(RuntimeOrigin::root(), 2, Some(limit)),
+			Error::AuthorizationNotFound,
+		);
+		assert_noop!(
+			TransactionStorage::set_authorization_rate(
+				RuntimeOrigin::root(),
+				1,
+				Some(RateLimit { bytes: 3000, period: 0 })
+			),
+			Error::BadRateLimit,
+		);
+		assert_ok!(TransactionStorage::set_authorization_rate(
+			RuntimeOrigin::root(),
+			1,
+			Some(limit)
+		));
+		System::assert_last_event(RuntimeEvent::TransactionStorage(Event::AuthorizationRateSet {
+			who: 1,
+			limit: Some(limit),
+		}));
+
+		// Blocks 0 and 1 are in the same period
+		let call = Call::store { data: vec![0u8; 2000] };
+		assert_ok!(TransactionStorage::pre_dispatch_signed(&1, &call));
+		assert_noop!(TransactionStorage::validate_signed(&1, &call), RATE_LIMITED);
+		assert_noop!(TransactionStorage::pre_dispatch_signed(&1, &call), RATE_LIMITED);
+		assert_ok!(TransactionStorage::pre_dispatch_signed(
+			&1,
+			&Call::store { data: vec![0u8; 1000] }
+		));
+		assert_eq!(
+			TransactionStorage::account_authorization_extent(1),
+			AuthorizationExtent { transactions: 8, bytes: 7000 },
+		);
+
+		// Transfers count towards the limit
+		run_to_block(2, || None);
+		assert_ok!(TransactionStorage::pre_dispatch_signed(&1, &call));
+		assert_noop!(
+			TransactionStorage::transfer_quota(RuntimeOrigin::signed(1), 2, 1, 2000),
+			Error::InsufficientAuthorization,
+		);
+		assert_ok!(TransactionStorage::transfer_quota(RuntimeOrigin::signed(1), 2, 1, 1000));
+
+		assert_ok!(TransactionStorage::set_authorization_rate(RuntimeOrigin::root(), 1, None));
+		let small_call = Call::store { data: vec![0u8; 1000] };
+		assert_ok!(TransactionStorage::pre_dispatch_signed(&1, &small_call));
+		assert_eq!(
+			TransactionStorage::account_authorization_extent(1),
+			AuthorizationExtent { transactions: 5, bytes: 3000 },
+		);
+
+		// Removed along with the authorization, once it expires
+		assert_ok!(TransactionStorage::set_authorization_rate(
+			RuntimeOrigin::root(),
+			1,
+			Some(limit)
+		));
+		assert_ok!(TransactionStorage::pre_dispatch_signed(&1, &call));
+		assert_ok!(TransactionStorage::pre_dispatch_signed(&1, &small_call));
+		assert_eq!(
+			TransactionStorage::account_authorization_extent(1),
+			AuthorizationExtent { transactions: 0, bytes: 0 },
+		);
+		assert!(AuthorizationRates::contains_key(1));
+		run_to_block(11, || None);
+		TransactionStorage::on_idle(11, Weight::MAX);
+		assert!(!AuthorizationRates::contains_key(1));
+	});
+}
+
+#[test]
+fn refreshes_standing_authorizations() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		let quota = StandingQuota { transactions: 2, bytes: 2000, period: 5 };
+		assert_noop!(
+			TransactionStorage::set_standing_authorization(
+				RuntimeOrigin::signed(1),
+				1,
+				Some(quota)
+			),
+			DispatchError::BadOrigin,
+		);
+		assert_noop!(
+			TransactionStorage::set_standing_authorization(
+				RuntimeOrigin::root(),
+				1,
+				Some(StandingQuota { period: 0, ..quota })
+			),
+			Error::BadStandingQuota,
+		);
+		assert_ok!(TransactionStorage::set_standing_authorization(
+			RuntimeOrigin::root(),
+			1,
+			Some(quota)
+		));
+		System::assert_last_event(RuntimeEvent::TransactionStorage(
+			Event::StandingAuthorizationSet { who: 1, quota: Some(quota) },
+		));
+
+		// The refresh is only written on first use
+		assert!(!Authorizations::contains_key(AuthorizationScope::Account(1)));
+		assert_eq!(
+			TransactionStorage::account_authorization_status(1),
+			Some(AuthorizationStatus { bytes_left: 2000, transactions_left: 2, expires_at: 5 }),
+		);
+		let call = Call::store { data: vec![0u8; 1000] };
+		assert_ok!(TransactionStorage::pre_dispatch_signed(&1, &call));
+		assert_eq!(
+			TransactionStorage::account_authorization_status(1),
+			Some(AuthorizationStatus { bytes_left: 1000, transactions_left: 1, expires_at: 5 }),
+		);
+		assert_eq!(System::providers(&1), 1);
+		assert_ok!(TransactionStorage::pre_dispatch_signed(&1, &call));
+		assert_eq!(System::providers(&1), 1);
+		assert_noop!(
+			TransactionStorage::pre_dispatch_signed(&1, &call),
+			InvalidTransaction::Payment,
+		);
+
+		// Refreshed in the next period
+		run_to_block(5, || None);
+		assert_eq!(
+			TransactionStorage::account_authorization_extent(1),
+			AuthorizationExtent { transactions: 2, bytes: 2000 },
+		);
+
+		// Larger authorizations are kept
+		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), 1, 10, 10000));
+		run_to_block(10, || None);
+		assert_ok!(TransactionStorage::pre_dispatch_signed(&1, &call));
+		assert_eq!(
+			TransactionStorage::account_authorization_status(1),
+			Some(AuthorizationStatus { bytes_left: 11000, transactions_left: 11, expires_at: 15 }),
+		);
+
+		assert_ok!(TransactionStorage::set_standing_authorization(RuntimeOrigin::root(), 1, None));
+		run_to_block(15, || None);
+		assert_eq!(TransactionStorage::account_authorization_status(1), None);
+	});
+}
+
+fn upload_root(chunks: &[Vec<u8>]) -> ContentHash {
+	let hashes = chunks.iter().map(|chunk| blake2_256(chunk).to_vec()).collect();
+	sp_io::trie::blake2_256_ordered_root(hashes, sp_runtime::StateVersion::V1).into()
+}
+
+#[test]
+fn uploads_chunked_data() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		let who = 1;
+		let chunks = vec![vec![1u8; 2000], vec![2u8; 2000], vec![3u8; 500]];
+		let root = upload_root(&chunks);
+		assert_ok!(TransactionStorage::begin_upload(RuntimeOrigin::signed(who), root, 4500, 3));
+		System::assert_last_event(RuntimeEvent::TransactionStorage(Event::UploadStarted {
+			session: 0,
+			who,
+			root,
+			size: 4500,
+			chunk_count: 3,
+		}));
+
+		// Chunks may span several blocks but must arrive in order
+		assert_noop!(
+			TransactionStorage::upload_chunk(RuntimeOrigin::signed(who), 0, 1, chunks[1].clone()),
+			Error::UnexpectedUploadChunk,
+		);
+		assert_ok!(TransactionStorage::upload_chunk(
+			RuntimeOrigin::signed(who),
+			0,
+			0,
+			chunks[0].clone()
+		));
+		assert_noop!(
+			TransactionStorage::finalize_upload(RuntimeOrigin::signed(who), 0),
+			Error::UploadIncomplete,
+		);
+		run_to_block(2, || None);
+		assert_ok!(TransactionStorage::upload_chunk(
+			RuntimeOrigin::signed(who),
+			0,
+			1,
+			chunks[1].clone()
+		));
+		assert_ok!(TransactionStorage::upload_chunk(
+			RuntimeOrigin::signed(who),
+			0,
+			2,
+			chunks[2].clone()
+		));
+		System::assert_last_event(RuntimeEvent::TransactionStorage(Event::UploadChunkStored {
+			session: 0,
+			chunk: 2,
+			index: 1,
+		}));
+
+		assert_noop!(
+			TransactionStorage::finalize_upload(RuntimeOrigin::signed(2), 0),
+			Error::NotUploadOwner,
+		);
+		assert_ok!(TransactionStorage::finalize_upload(RuntimeOrigin::signed(who), 0));
+		let receipt =
+			UploadReceipt { root, size: 4500, chunk_count: 3, first_block: 1, last_block: 2 };
+		System::assert_last_event(RuntimeEvent::TransactionStorage(Event::UploadFinalized {
+			session: 0,
+			receipt: receipt.clone(),
+		}));
+		assert!(!UploadSessions::contains_key(0));
+		assert_eq!(TransactionStorage::upload_receipt(root), Some(receipt));
+
+		// Each chunk can be verified against the root on its own
+		for (chunk, data) in chunks.iter().enumerate() {
+			let proof = TransactionStorage::upload_chunk_proof(root, chunk as u32).unwrap();
+			assert_eq!(proof.content_hash, blake2_256(data));
+			assert!(sp_io::trie::blake2_256_verify_proof(
+				root.into(),
+				&proof.proof,
+				&encode_index(chunk as u32),
+				&proof.content_hash,
+				sp_runtime::StateVersion::V1,
+			));
+		}
+		assert_eq!(TransactionStorage::upload_chunk_proof(root, 3), None);
+
+		// Receipt is kept until the last chunk expires
+		let proof_provider = || {
+			let block_num = System::block_number();
+			let parent_hash = System::parent_hash();
+			match block_num {
+				11 => Some(build_proof(parent_hash.as_ref(), vec![chunks[0].clone()]).unwrap()),
+				12 => Some(build_proof(parent_hash.as_ref(), chunks[1..].to_vec()).unwrap()),
+				_ => None,
+			}
+		};
+		run_to_block(12, proof_provider);
+		TransactionStorage::on_idle(12, Weight::MAX);
+		assert!(TransactionStorage::upload_receipt(root).is_some());
+		run_to_block(13, proof_provider);
+		TransactionStorage::on_idle(13, Weight::MAX);
+		assert_eq!(TransactionStorage::upload_receipt(root), None);
+		assert_eq!(TransactionStorage::upload_chunk_proof(root, 0), None);
+	});
+}
+
+#[test]
+fn checks_upload_parameters_and_root() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		let who = 1;
+		// Too many chunks
+		assert_noop!(
+			TransactionStorage::begin_upload(RuntimeOrigin::signed(who), [0; 32], 5000, 5),
+			Error::BadUploadParameters,
+		);
+		// Chunks can't hold the data
+		assert_noop!(
+			TransactionStorage::begin_upload(
+				RuntimeOrigin::signed(who),
+				[0; 32],
+				MAX_DATA_SIZE as u64 + 1,
+				1
+			),
+			Error::BadUploadParameters,
+		);
+
+		// Supplied chunks don't match the committed root
+		let root = upload_root(&[vec![1u8; 2000]]);
+		assert_ok!(TransactionStorage::begin_upload(RuntimeOrigin::signed(who), root, 2000, 1));
+		assert_noop!(
+			TransactionStorage::upload_chunk(RuntimeOrigin::signed(who), 0, 0, vec![1u8; 2001]),
+			Error::UnexpectedUploadChunk,
+		);
+		assert_ok!(TransactionStorage::upload_chunk(
+			RuntimeOrigin::signed(who),
+			0,
+			0,
+			vec![2u8; 2000]
+		));
+		assert_noop!(
+			TransactionStorage::finalize_upload(RuntimeOrigin::signed(who), 0),
+			Error::UploadRootMismatch,
+		);
+	});
+}
+
+#[test]
+fn upload_chunks_use_account_authorization() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		let who = 1;
+		let chunks = vec![vec![1u8; 2000], vec![2u8; 1000]];
+		let root = upload_root(&chunks);
+		let begin = Call::begin_upload { root, size: 3000, chunk_count: 2 };
+		assert_noop!(
+			TransactionStorage::pre_dispatch_signed(&who, &begin),
+			InvalidTransaction::Payment,
+		);
+		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), who, 2, 3000));
+		// Starting an upload uses one transaction on top of those for the chunks
+		assert_noop!(
+			TransactionStorage::pre_dispatch_signed(&who, &begin),
+			InvalidTransaction::Payment,
+		);
+		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), who, 1, 1000));
+		assert_ok!(TransactionStorage::pre_dispatch_signed(&who, &begin));
+		assert_eq!(
+			TransactionStorage::account_authorization_extent(who),
+			AuthorizationExtent { transactions: 2, bytes: 4000 }
+		);
+		assert_ok!(Into::<RuntimeCall>::into(begin).dispatch(RuntimeOrigin::signed(who)));
+
+		let chunk = Call::upload_chunk { session: 0, chunk: 1, data: chunks[1].clone() };
+		assert_noop!(
+			TransactionStorage::pre_dispatch_signed(&who, &chunk),
+			UNEXPECTED_UPLOAD_CHUNK,
+		);
+		let chunk = Call::upload_chunk { session: 0, chunk: 0, data: chunks[0].clone() };
+		assert_noop!(TransactionStorage::pre_dispatch_signed(&2, &chunk), UPLOAD_NOT_FOUND);
+		assert_ok!(TransactionStorage::pre_dispatch_signed(&who, &chunk));
+		assert_eq!(
+			TransactionStorage::account_authorization_extent(who),
+			AuthorizationExtent { transactions: 1, bytes: 2000 }
+		);
+		assert_ok!(Into::<RuntimeCall>::into(chunk).dispatch(RuntimeOrigin::signed(who)));
+
+		let finalize = Call::finalize_upload { session: 0 };
+		assert_noop!(TransactionStorage::pre_dispatch_signed(&who, &finalize), UPLOAD_INCOMPLETE);
+	});
+}
+
+#[test]
+fn resumes_timed_out_uploads() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		let who = 1;
+		let chunks = vec![vec![1u8; 2000], vec![2u8; 1000]];
+		let root = upload_root(&chunks);
+		assert_ok!(TransactionStorage::begin_upload(RuntimeOrigin::signed(who), root, 3000, 2));
+		assert_ok!(TransactionStorage::upload_chunk(
+			RuntimeOrigin::signed(who),
+			0,
+			0,
+			chunks[0].clone()
+		));
+
+		// No chunk for `UploadTimeout` blocks
+		run_to_block(6, || None);
+		let chunk = Call::upload_chunk { session: 0, chunk: 1, data: chunks[1].clone() };
+		assert_noop!(TransactionStorage::pre_dispatch_signed(&who, &chunk), UPLOAD_TIMED_OUT);
+		assert_noop!(
+			TransactionStorage::upload_chunk(RuntimeOrigin::signed(who), 0, 1, chunks[1].clone()),
+			Error::UploadTimedOut,
+		);
+
+		assert_noop!(
+			TransactionStorage::resume_upload(RuntimeOrigin::signed(2), 0),
+			Error::NotUploadOwner,
+		);
+		assert_ok!(TransactionStorage::resume_upload(RuntimeOrigin::signed(who), 0));
+		System::assert_last_event(RuntimeEvent::TransactionStorage(Event::UploadResumed {
+			session: 0,
+			expires_at: 11,
+		}));
+		assert_ok!(TransactionStorage::upload_chunk(
+			RuntimeOrigin::signed(who),
+			0,
+			1,
+			chunks[1].clone()
+		));
+		assert_ok!(TransactionStorage::finalize_upload(RuntimeOrigin::signed(who), 0));
+	});
+}
+
+#[test]
+fn abandoned_uploads_removed_on_idle() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		let who = 1;
+		assert_ok!(TransactionStorage::begin_upload(RuntimeOrigin::signed(who), [0; 32], 2000, 1));
+
+		// Timed out, but may still be resumed
+		run_to_block(10, || None);
+		TransactionStorage::on_idle(10, Weight::MAX);
+		assert!(UploadSessions::contains_key(0));
+
+		run_to_block(11, || None);
+		// No weight, nothing removed
+		TransactionStorage::on_idle(11, Weight::zero());
+		assert!(UploadSessions::contains_key(0));
+
+		TransactionStorage::on_idle(11, Weight::MAX);
+		System::assert_last_event(RuntimeEvent::TransactionStorage(Event::UploadAbandoned {
+			session: 0,
+			who,
+		}));
+		assert!(!UploadSessions::contains_key(0));
+		assert_noop!(
+			TransactionStorage::resume_upload(RuntimeOrigin::signed(who), 0),
+			Error::UploadNotFound,
+		);
+		// There was no authorization to refund
+		assert!(!Authorizations::contains_key(AuthorizationScope::Account(who)));
+	});
+}
+
+#[test]
+fn abandoned_upload_refunds_begin_transaction() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1, || None);
+		let who = 1;
+		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), who, 2, 2000));
+		let call = Call::begin_upload { root: [0; 32], size: 2000, chunk_count: 1 };
+		assert_ok!(TransactionStorage::pre_dispatch_signed(&who, &call));
+		assert_ok!(TransactionStorage::begin_upload(RuntimeOrigin::signed(who), [0; 32], 2000, 1));
+		assert_eq!(
+			TransactionStorage::account_authorization_extent(who),
+			AuthorizationExtent { transactions: 1, bytes: 2000 },
+		);
+
+		// Extend the authorization past the removal of the upload
+		run_to_block(5, || None);
+		assert_ok!(TransactionStorage::authorize_account(RuntimeOrigin::root(), who, 1, 1000));
+		run_to_block(11, || None);
+		TransactionStorage::on_idle(11, Weight::MAX);
+		System::assert_last_event(RuntimeEvent::TransactionStorage(Event::UploadAbandoned {
+			session: 0,
+			who,
+		}));
+		assert_eq!(
+			TransactionStorage::account_authorization_extent(who),
+			AuthorizationExtent { transactions: 3, bytes: 3000 },
+		);
 	});
 }
@@ -22,6 +22,9 @@
 //! WORST CASE MAP SIZE: `1000000`
 //! HOSTNAME: `runner-e8ezs4ez-project-145-concurrent-0`, CPU: `Intel(R) Xeon(R) CPU @ 2.60GHz`
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 1024
+//!
+//! Weights marked as placeholders have not been benchmarked yet, and must be regenerated with
+//! the command below before being relied upon.
 
 // Executed Command:
 // ./target/production/substrate
@@ -59,6 +62,29 @@ pub trait WeightInfo {
 	fn authorize_preimage() -> Weight;
 	fn remove_expired_account_authorization() -> Weight;
 	fn remove_expired_preimage_authorization() -> Weight;
+	fn begin_upload() -> Weight;
+	fn upload_chunk(l: u32, ) -> Weight;
+	fn finalize_upload(c: u32, ) -> Weight;
+	fn store_in_namespace(l: u32, ) -> Weight;
+	fn set_namespace_config() -> Weight;
+	fn retract() -> Weight;
+	fn resume_upload() -> Weight;
+	fn store_operational(l: u32, ) -> Weight;
+	fn transfer_quota() -> Weight;
+	fn delegate() -> Weight;
+	fn revoke_delegation() -> Weight;
+	fn store_with_attestation(l: u32, ) -> Weight;
+	fn store_encoded(l: u32, ) -> Weight;
+	fn claim_name() -> Weight;
+	fn point_name() -> Weight;
+	fn release_name() -> Weight;
+	fn pin() -> Weight;
+	fn set_usage_threshold() -> Weight;
+	fn set_authorization_rate() -> Weight;
+	fn commit() -> Weight;
+	fn reveal(l: u32, ) -> Weight;
+	fn force_set_authorizations(n: u32, ) -> Weight;
+	fn set_standing_authorization() -> Weight;
 }
 
 /// Weights for pallet_transaction_storage using the Substrate node and recommended hardware.
@@ -70,6 +96,8 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: TransactionStorage EntryFee (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
 	/// Storage: TransactionStorage BlockTransactions (r:1 w:1)
 	/// Proof: TransactionStorage BlockTransactions (max_values: Some(1), max_size: Some(36866), added: 37361, mode: MaxEncodedLen)
+	/// Placeholder, not yet re-benchmarked. Includes the `ContentIndex` and `ContentCopies` reads
+	/// and writes, and the `Uploaders` write, added since.
 	/// The range of component `l` is `[1, 8388608]`.
 	fn store(l: u32, ) -> Weight {
 		// Proof Size summary in bytes:
@@ -79,8 +107,8 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		Weight::from_parts(35_489_000, 38351)
 			// Standard Error: 11
 			.saturating_add(Weight::from_parts(6_912, 0).saturating_mul(l.into()))
-			.saturating_add(T::DbWeight::get().reads(3_u64))
-			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
 	}
 	/// Storage: TransactionStorage Transactions (r:1 w:0)
 	/// Proof: TransactionStorage Transactions (max_values: None, max_size: Some(36886), added: 39361, mode: MaxEncodedLen)
@@ -90,14 +118,16 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: TransactionStorage EntryFee (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
 	/// Storage: TransactionStorage BlockTransactions (r:1 w:1)
 	/// Proof: TransactionStorage BlockTransactions (max_values: Some(1), max_size: Some(36866), added: 37361, mode: MaxEncodedLen)
+	/// Placeholder, not yet re-benchmarked. Includes the `ContentIndex` and `ContentCopies` reads
+	/// and writes, and the `Uploaders` write, added since.
 	fn renew() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `326`
 		//  Estimated: `40351`
 		// Minimum execution time: 48_244_000 picoseconds.
 		Weight::from_parts(50_939_000, 40351)
-			.saturating_add(T::DbWeight::get().reads(4_u64))
-			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
 	}
 	/// Storage: TransactionStorage ProofChecked (r:1 w:1)
 	/// Proof: TransactionStorage ProofChecked (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
@@ -130,6 +160,157 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	fn remove_expired_preimage_authorization() -> Weight {
 		Weight::from_parts(1_000, 1_000)
 	}
+	/// Placeholder, not yet benchmarked.
+	fn begin_upload() -> Weight {
+		Weight::from_parts(20_000_000, 1_000)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Placeholder, not yet benchmarked. Based on the weight of `store`.
+	/// The range of component `l` is `[1, 8388608]`.
+	fn upload_chunk(l: u32, ) -> Weight {
+		Weight::from_parts(35_489_000, 38351)
+			.saturating_add(Weight::from_parts(6_912, 0).saturating_mul(l.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Placeholder, not yet benchmarked. Scales with the number of chunk hashes, which are
+	/// read from the session, pushed to a trie and written to the receipt.
+	/// The range of component `c` is `[1, 1024]`.
+	fn finalize_upload(c: u32, ) -> Weight {
+		Weight::from_parts(30_000_000, 1_000)
+			.saturating_add(Weight::from_parts(2_000_000, 32).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Placeholder, not yet benchmarked. Based on the weight of `store`.
+	/// The range of component `l` is `[1, 8388608]`.
+	fn store_in_namespace(l: u32, ) -> Weight {
+		Weight::from_parts(35_489_000, 38351)
+			.saturating_add(Weight::from_parts(6_912, 0).saturating_mul(l.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Placeholder, not yet benchmarked.
+	fn set_namespace_config() -> Weight {
+		Weight::from_parts(20_000_000, 1_000)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Placeholder, not yet benchmarked.
+	fn retract() -> Weight {
+		Weight::from_parts(20_000_000, 1_000)
+			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+	}
+	/// Placeholder, not yet benchmarked.
+	fn resume_upload() -> Weight {
+		Weight::from_parts(20_000_000, 1_000)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Placeholder, not yet benchmarked. Based on the weight of `store`.
+	/// The range of component `l` is `[1, 8388608]`.
+	fn store_operational(l: u32, ) -> Weight {
+		Weight::from_parts(35_489_000, 38351)
+			.saturating_add(Weight::from_parts(6_912, 0).saturating_mul(l.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Placeholder, not yet benchmarked.
+	fn transfer_quota() -> Weight {
+		Weight::from_parts(20_000_000, 1_000)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Placeholder, not yet benchmarked.
+	fn delegate() -> Weight {
+		Weight::from_parts(20_000_000, 1_000)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Placeholder, not yet benchmarked.
+	fn revoke_delegation() -> Weight {
+		Weight::from_parts(20_000_000, 1_000)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Placeholder, not yet benchmarked. Based on the weight of `store`.
+	/// The range of component `l` is `[1, 8388608]`.
+	fn store_with_attestation(l: u32, ) -> Weight {
+		Weight::from_parts(35_489_000, 38351)
+			.saturating_add(Weight::from_parts(6_912, 0).saturating_mul(l.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Placeholder, not yet benchmarked. Based on the weight of `store`.
+	/// The range of component `l` is `[17, 8388608]`.
+	fn store_encoded(l: u32, ) -> Weight {
+		Weight::from_parts(35_489_000, 38351)
+			.saturating_add(Weight::from_parts(6_912, 0).saturating_mul(l.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Placeholder, not yet benchmarked.
+	fn claim_name() -> Weight {
+		Weight::from_parts(20_000_000, 1_000)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Placeholder, not yet benchmarked.
+	fn point_name() -> Weight {
+		Weight::from_parts(20_000_000, 1_000)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Placeholder, not yet benchmarked.
+	fn release_name() -> Weight {
+		Weight::from_parts(20_000_000, 1_000)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Placeholder, not yet benchmarked. Based on the weight of `renew`.
+	fn pin() -> Weight {
+		Weight::from_parts(50_939_000, 40351)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Placeholder, not yet benchmarked.
+	fn set_usage_threshold() -> Weight {
+		Weight::from_parts(20_000_000, 1_000)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Placeholder, not yet benchmarked.
+	fn set_authorization_rate() -> Weight {
+		Weight::from_parts(20_000_000, 1_000)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Placeholder, not yet benchmarked.
+	fn commit() -> Weight {
+		Weight::from_parts(20_000_000, 1_000)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Placeholder, not yet benchmarked. Based on the weight of `store`.
+	fn reveal(l: u32, ) -> Weight {
+		Weight::from_parts(35_489_000, 38351)
+			.saturating_add(Weight::from_parts(6_912, 0).saturating_mul(l.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+	}
+	/// Placeholder, not yet benchmarked.
+	/// The range of component `n` is `[0, 1024]`.
+	fn force_set_authorizations(n: u32, ) -> Weight {
+		Weight::from_parts(20_000_000, 1_000)
+			.saturating_add(Weight::from_parts(5_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+	}
+	/// Placeholder, not yet benchmarked.
+	fn set_standing_authorization() -> Weight {
+		Weight::from_parts(20_000_000, 1_000)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -140,6 +321,8 @@ impl WeightInfo for () {
 	/// Proof: TransactionStorage EntryFee (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
 	/// Storage: TransactionStorage BlockTransactions (r:1 w:1)
 	/// Proof: TransactionStorage BlockTransactions (max_values: Some(1), max_size: Some(36866), added: 37361, mode: MaxEncodedLen)
+	/// Placeholder, not yet re-benchmarked. Includes the `ContentIndex` and `ContentCopies` reads
+	/// and writes, and the `Uploaders` write, added since.
 	/// The range of component `l` is `[1, 8388608]`.
 	fn store(l: u32, ) -> Weight {
 		// Proof Size summary in bytes:
@@ -149,8 +332,8 @@ impl WeightInfo for () {
 		Weight::from_parts(35_489_000, 38351)
 			// Standard Error: 11
 			.saturating_add(Weight::from_parts(6_912, 0).saturating_mul(l.into()))
-			.saturating_add(RocksDbWeight::get().reads(3_u64))
-			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
 	}
 	/// Storage: TransactionStorage Transactions (r:1 w:0)
 	/// Proof: TransactionStorage Transactions (max_values: None, max_size: Some(36886), added: 39361, mode: MaxEncodedLen)
@@ -160,14 +343,16 @@ impl WeightInfo for () {
 	/// Proof: TransactionStorage EntryFee (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
 	/// Storage: TransactionStorage BlockTransactions (r:1 w:1)
 	/// Proof: TransactionStorage BlockTransactions (max_values: Some(1), max_size: Some(36866), added: 37361, mode: MaxEncodedLen)
+	/// Placeholder, not yet re-benchmarked. Includes the `ContentIndex` and `ContentCopies` reads
+	/// and writes, and the `Uploaders` write, added since.
 	fn renew() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `326`
 		//  Estimated: `40351`
 		// Minimum execution time: 48_244_000 picoseconds.
 		Weight::from_parts(50_939_000, 40351)
-			.saturating_add(RocksDbWeight::get().reads(4_u64))
-			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
 	}
 	/// Storage: TransactionStorage ProofChecked (r:1 w:1)
 	/// Proof: TransactionStorage ProofChecked (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
@@ -200,4 +385,162 @@ impl WeightInfo for () {
 	fn remove_expired_preimage_authorization() -> Weight {
 		Weight::from_parts(1_000, 1_000)
 	}
+	/// Placeholder, not yet benchmarked.
+	fn begin_upload() -> Weight {
+		Weight::from_parts(20_000_000, 1_000)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Placeholder, not yet benchmarked. Based on the weight of `store`.
+	/// Placeholder, not yet benchmarked. Based on the weight of `store`.
+	/// The range of component `l` is `[1, 8388608]`.
+	fn upload_chunk(l: u32, ) -> Weight {
+		Weight::from_parts(35_489_000, 38351)
+			.saturating_add(Weight::from_parts(6_912, 0).saturating_mul(l.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Placeholder, not yet benchmarked. Scales with the number of chunk hashes, which are
+	/// read from the session, pushed to a trie and written to the receipt.
+	/// The range of component `c` is `[1, 1024]`.
+	fn finalize_upload(c: u32, ) -> Weight {
+		Weight::from_parts(30_000_000, 1_000)
+			.saturating_add(Weight::from_parts(2_000_000, 32).saturating_mul(c.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Placeholder, not yet benchmarked. Based on the weight of `store`.
+	/// Placeholder, not yet benchmarked. Based on the weight of `store`.
+	/// The range of component `l` is `[1, 8388608]`.
+	fn store_in_namespace(l: u32, ) -> Weight {
+		Weight::from_parts(35_489_000, 38351)
+			.saturating_add(Weight::from_parts(6_912, 0).saturating_mul(l.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Placeholder, not yet benchmarked.
+	fn set_namespace_config() -> Weight {
+		Weight::from_parts(20_000_000, 1_000)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Placeholder, not yet benchmarked.
+	fn retract() -> Weight {
+		Weight::from_parts(20_000_000, 1_000)
+			.saturating_add(RocksDbWeight::get().reads(11_u64))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+	}
+	/// Placeholder, not yet benchmarked.
+	fn resume_upload() -> Weight {
+		Weight::from_parts(20_000_000, 1_000)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Placeholder, not yet benchmarked. Based on the weight of `store`.
+	/// Placeholder, not yet benchmarked. Based on the weight of `store`.
+	/// The range of component `l` is `[1, 8388608]`.
+	fn store_operational(l: u32, ) -> Weight {
+		Weight::from_parts(35_489_000, 38351)
+			.saturating_add(Weight::from_parts(6_912, 0).saturating_mul(l.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Placeholder, not yet benchmarked.
+	fn transfer_quota() -> Weight {
+		Weight::from_parts(20_000_000, 1_000)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Placeholder, not yet benchmarked.
+	fn delegate() -> Weight {
+		Weight::from_parts(20_000_000, 1_000)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Placeholder, not yet benchmarked.
+	fn revoke_delegation() -> Weight {
+		Weight::from_parts(20_000_000, 1_000)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Placeholder, not yet benchmarked. Based on the weight of `store`.
+	/// Placeholder, not yet benchmarked. Based on the weight of `store`.
+	/// The range of component `l` is `[1, 8388608]`.
+	fn store_with_attestation(l: u32, ) -> Weight {
+		Weight::from_parts(35_489_000, 38351)
+			.saturating_add(Weight::from_parts(6_912, 0).saturating_mul(l.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Placeholder, not yet benchmarked. Based on the weight of `store`.
+	/// Placeholder, not yet benchmarked. Based on the weight of `store`.
+	/// The range of component `l` is `[17, 8388608]`.
+	fn store_encoded(l: u32, ) -> Weight {
+		Weight::from_parts(35_489_000, 38351)
+			.saturating_add(Weight::from_parts(6_912, 0).saturating_mul(l.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Placeholder, not yet benchmarked.
+	fn claim_name() -> Weight {
+		Weight::from_parts(20_000_000, 1_000)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Placeholder, not yet benchmarked.
+	fn point_name() -> Weight {
+		Weight::from_parts(20_000_000, 1_000)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Placeholder, not yet benchmarked.
+	fn release_name() -> Weight {
+		Weight::from_parts(20_000_000, 1_000)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Placeholder, not yet benchmarked. Based on the weight of `renew`.
+	/// Placeholder, not yet benchmarked. Based on the weight of `renew`.
+	fn pin() -> Weight {
+		Weight::from_parts(50_939_000, 40351)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Placeholder, not yet benchmarked.
+	fn set_usage_threshold() -> Weight {
+		Weight::from_parts(20_000_000, 1_000)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Placeholder, not yet benchmarked.
+	fn set_authorization_rate() -> Weight {
+		Weight::from_parts(20_000_000, 1_000)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Placeholder, not yet benchmarked.
+	fn commit() -> Weight {
+		Weight::from_parts(20_000_000, 1_000)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Placeholder, not yet benchmarked. Based on the weight of `store`.
+	/// Placeholder, not yet benchmarked. Based on the weight of `store`.
+	fn reveal(l: u32, ) -> Weight {
+		Weight::from_parts(35_489_000, 38351)
+			.saturating_add(Weight::from_parts(6_912, 0).saturating_mul(l.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+	}
+	/// Placeholder, not yet benchmarked.
+	/// The range of component `n` is `[0, 1024]`.
+	fn force_set_authorizations(n: u32, ) -> Weight {
+		Weight::from_parts(20_000_000, 1_000)
+			.saturating_add(Weight::from_parts(5_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(n.into())))
+	}
+	/// Placeholder, not yet benchmarked.
+	fn set_standing_authorization() -> Weight {
+		Weight::from_parts(20_000_000, 1_000)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
 }
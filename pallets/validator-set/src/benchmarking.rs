@@ -63,5 +63,37 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn set_offline_threshold() -> Result<(), BenchmarkError> {
+		let origin = T::AddRemoveOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Stop("unable to compute origin"))?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, Some(1));
+
+		assert_last_event::<T>(Event::OfflineThresholdSet { threshold: Some(1) }.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn reinstate_validator() -> Result<(), BenchmarkError> {
+		let origin = T::AddRemoveOrigin::try_successful_origin()
+			.map_err(|_| BenchmarkError::Stop("unable to compute origin"))?;
+		let who: T::AccountId = account("validator", 0, SEED);
+
+		// The last active validator can't be suspended
+		for validator in [account("validator", 1, SEED), who.clone()] {
+			Pallet::<T>::add_validator(origin.clone(), validator)
+				.map_err(|_| BenchmarkError::Stop("unable to add validator"))?;
+		}
+		ensure!(Pallet::<T>::do_suspend_validator(&who), "unable to suspend validator");
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, who.clone());
+
+		assert_last_event::<T>(Event::ValidatorReinstated(who).into());
+		Ok(())
+	}
+
 	impl_benchmark_test_suite!(ValidatorSet, crate::mock::new_test_ext(), crate::mock::Test);
 }
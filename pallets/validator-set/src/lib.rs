@@ -18,18 +18,33 @@
 //! privileged origin.
 //!
 //! Provides a [`SessionManager`] implementation which returns the current validator set from
-//! `new_session`. Also provides an [`OnOffenceHandler`] implementation which removes the offending
-//! validators from the set (if they would be slashed) and temporarily disables them according to
-//! the [`DisableStrategy`].
+//! `new_session`. Also provides an [`OnOffenceHandler`] implementation which suspends the
+//! offending validators (if they would be slashed) and temporarily disables them according to the
+//! [`DisableStrategy`]. There is no staking, so nothing is actually slashed. Suspended validators
+//! remain in the set but are excluded from new sessions until the privileged origin either
+//! reinstates them with [`reinstate_validator`](Pallet::reinstate_validator) or removes them. The
+//! last validator that is not suspended is never suspended, as block production would stall
+//! without any validators.
+//!
+//! Failing to provide a storage proof is not an offence. A block that lacks a required storage
+//! proof, or includes an invalid one, is itself invalid, so it is rejected by the other
+//! validators rather than reported; the author just misses its slot.
 //!
 //! Adding a validator to the set increments the validator account's provider reference count. This
 //! allows the validator to set their session keys with
 //! [`set_keys`](pallet_session::Pallet::set_keys). When a validator is removed, either explicitly
-//! via [`remove_validator`](Pallet::remove_validator) or implicitly due to an offence, the
+//! via [`remove_validator`](Pallet::remove_validator) or implicitly due to being offline, the
 //! validator's session keys are automatically purged and the validator account's provider
 //! reference count is decremented again. Note that failure to decrement the provider reference
 //! count does not cause removal to fail; the provider reference is just leaked.
 //!
+//! Validators that are persistently offline can be removed automatically. Unresponsiveness
+//! offences (such as those reported by `pallet_im_online`) should be reported through
+//! [`ReportOffline`], which counts the consecutive sessions each validator has been reported
+//! offline in. A validator reported in `OfflineThreshold` consecutive sessions is removed, unless
+//! this would leave fewer than `MinActiveValidators` validators that are not suspended; the
+//! threshold is set with [`set_offline_threshold`](Pallet::set_offline_threshold).
+//!
 //! This pallet directly depends on [`pallet_session`] and [`pallet_session::historical`].
 //! [`pallet_session::Config::ValidatorId`] must be [`AccountId`](frame_system::Config::AccountId)
 //! and [`pallet_session::Config::ValidatorIdOf`] must be [`ConvertInto`].
@@ -58,10 +73,12 @@ use sp_runtime::{
 	Perbill, Saturating,
 };
 use sp_staking::{
-	offence::{DisableStrategy, OffenceDetails, OnOffenceHandler},
+	offence::{
+		DisableStrategy, Offence, OffenceDetails, OffenceError, OnOffenceHandler, ReportOffence,
+	},
 	SessionIndex,
 };
-use sp_std::vec::Vec;
+use sp_std::{marker::PhantomData, vec::Vec};
 pub use weights::WeightInfo;
 
 const LOG_TARGET: &str = "runtime::validator-set";
@@ -104,17 +121,23 @@ pub mod pallet {
 		/// by a validator.
 		#[pallet::constant]
 		type SetKeysCooldownBlocks: Get<BlockNumberFor<Self>>;
+
+		/// Minimum number of validators that are not suspended. Offline validators are not
+		/// removed automatically if this would leave fewer.
+		#[pallet::constant]
+		type MinActiveValidators: Get<u32>;
 	}
 
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
-	/// Validator set. Changes to this will take effect in the session after next.
+	/// Validator set, including suspended validators. Changes to this will take effect in the
+	/// session after next.
 	#[pallet::storage]
 	pub(super) type Validators<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::AccountId, Validator<BlockNumberFor<T>>, OptionQuery>;
 
-	/// Number of validators in `Validators`.
+	/// Number of validators in `Validators`, including suspended validators.
 	#[pallet::storage]
 	pub(super) type NumValidators<T: Config> = StorageValue<_, u32, ValueQuery>;
 
@@ -129,6 +152,27 @@ pub mod pallet {
 	pub(super) type NextDisabledValidators<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
 
+	/// Validators suspended in response to an offence, pending review by the privileged origin.
+	/// Suspension takes effect in the session after next.
+	#[pallet::storage]
+	pub(super) type SuspendedValidators<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+	/// Number of validators in `SuspendedValidators`.
+	#[pallet::storage]
+	pub(super) type NumSuspendedValidators<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// Number of consecutive sessions a validator may be reported offline in before it is
+	/// removed. If `None`, offline validators are never removed automatically.
+	#[pallet::storage]
+	pub(super) type OfflineThreshold<T: Config> = StorageValue<_, u32, OptionQuery>;
+
+	/// The last session each validator was reported offline in, and the number of consecutive
+	/// sessions up to and including it that the validator was reported offline in.
+	#[pallet::storage]
+	pub(super) type OfflineSessions<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, (SessionIndex, u32), OptionQuery>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -136,6 +180,14 @@ pub mod pallet {
 		ValidatorAdded(T::AccountId),
 		/// Validator removed. Effective in session after next.
 		ValidatorRemoved(T::AccountId),
+		/// Validator reported offline, in `sessions` consecutive sessions.
+		ValidatorOffline { who: T::AccountId, sessions: u32 },
+		/// The offline threshold was set, or cleared if `threshold` is `None`.
+		OfflineThresholdSet { threshold: Option<u32> },
+		/// Validator suspended in response to an offence. Effective in session after next.
+		ValidatorSuspended(T::AccountId),
+		/// Suspended validator reinstated. Effective in session after next.
+		ValidatorReinstated(T::AccountId),
 	}
 
 	#[pallet::error]
@@ -146,6 +198,8 @@ pub mod pallet {
 		NotAValidator,
 		/// Adding the validator would take the validator count above the maximum.
 		TooManyValidators,
+		/// Validator is not suspended.
+		NotSuspended,
 	}
 
 	#[pallet::genesis_config]
@@ -203,12 +257,59 @@ pub mod pallet {
 			Self::deposit_event(Event::ValidatorRemoved(who));
 			Ok(())
 		}
+
+		/// Set or clear the number of consecutive sessions a validator may be reported offline in
+		/// before it is removed.
+		///
+		/// The origin for this call must be the pallet's `AddRemoveOrigin`. Emits
+		/// [`OfflineThresholdSet`](Event::OfflineThresholdSet) when successful.
+		#[pallet::call_index(2)]
+		#[pallet::weight(<T as Config>::WeightInfo::set_offline_threshold())]
+		pub fn set_offline_threshold(
+			origin: OriginFor<T>,
+			threshold: Option<u32>,
+		) -> DispatchResult {
+			T::AddRemoveOrigin::ensure_origin(origin)?;
+			OfflineThreshold::<T>::set(threshold);
+			Self::deposit_event(Event::OfflineThresholdSet { threshold });
+			Ok(())
+		}
+
+		/// Reinstate a validator that was suspended in response to an offence. Suspended
+		/// validators can instead be removed with [`remove_validator`](Self::remove_validator).
+		///
+		/// The reinstatement will take effect the session after next.
+		///
+		/// The origin for this call must be the pallet's `AddRemoveOrigin`. Emits
+		/// [`ValidatorReinstated`](Event::ValidatorReinstated) when successful.
+		#[pallet::call_index(3)]
+		#[pallet::weight(<T as Config>::WeightInfo::reinstate_validator())]
+		pub fn reinstate_validator(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::AddRemoveOrigin::ensure_origin(origin)?;
+			ensure!(SuspendedValidators::<T>::take(&who).is_some(), Error::<T>::NotSuspended);
+			NumSuspendedValidators::<T>::mutate(|num| *num -= 1);
+			Self::deposit_event(Event::ValidatorReinstated(who));
+			Ok(())
+		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
+	/// Returns the validators that are not suspended.
 	pub fn validators() -> Vec<T::AccountId> {
-		Validators::<T>::iter_keys().collect()
+		Validators::<T>::iter_keys()
+			.filter(|who| !SuspendedValidators::<T>::contains_key(who))
+			.collect()
+	}
+
+	/// Returns the validators that are suspended, pending review.
+	pub fn suspended_validators() -> Vec<T::AccountId> {
+		SuspendedValidators::<T>::iter_keys().collect()
+	}
+
+	/// Returns the number of validators that are not suspended.
+	fn num_active_validators() -> u32 {
+		NumValidators::<T>::get().saturating_sub(NumSuspendedValidators::<T>::get())
 	}
 
 	fn do_add_validator(who: &T::AccountId) -> DispatchResult {
@@ -240,6 +341,10 @@ impl<T: Config> Pallet<T> {
 			return false
 		}
 		NumValidators::<T>::mutate(|num| *num -= 1);
+		if SuspendedValidators::<T>::take(who).is_some() {
+			NumSuspendedValidators::<T>::mutate(|num| *num -= 1);
+		}
+		OfflineSessions::<T>::remove(who);
 
 		// Decrement who's provider reference count. Purge who's session keys first as
 		// dec_providers will fail if there are any consumers.
@@ -263,6 +368,66 @@ impl<T: Config> Pallet<T> {
 		true
 	}
 
+	/// Returns `false` if `who` is not a validator, is already suspended, or is the last validator
+	/// that is not suspended.
+	fn do_suspend_validator(who: &T::AccountId) -> bool {
+		if !Validators::<T>::contains_key(who) || SuspendedValidators::<T>::contains_key(who) {
+			return false
+		}
+		if Self::num_active_validators() <= 1 {
+			log::warn!(
+				target: LOG_TARGET,
+				"Not suspending validator {:?}, it is the last active validator", who
+			);
+			return false
+		}
+		SuspendedValidators::<T>::insert(who, ());
+		NumSuspendedValidators::<T>::mutate(|num| *num += 1);
+		true
+	}
+
+	/// Returns the number of consecutive sessions `who` has been reported offline in, up to and
+	/// including the last session it was reported in.
+	pub fn offline_sessions(who: &T::AccountId) -> u32 {
+		OfflineSessions::<T>::get(who).map_or(0, |(_, sessions)| sessions)
+	}
+
+	/// Note that `who` was reported offline in `session`, removing it if it has now been reported
+	/// offline in `OfflineThreshold` consecutive sessions and enough other validators are active.
+	fn note_offline(who: &T::AccountId, session: SessionIndex) {
+		if !Validators::<T>::contains_key(who) {
+			return
+		}
+		let sessions = OfflineSessions::<T>::mutate(who, |offline| {
+			let sessions = match *offline {
+				Some((last, sessions)) if last == session => sessions,
+				Some((last, sessions)) if last.saturating_add(1) == session =>
+					sessions.saturating_add(1),
+				_ => 1,
+			};
+			*offline = Some((session, sessions));
+			sessions
+		});
+		Self::deposit_event(Event::ValidatorOffline { who: who.clone(), sessions });
+
+		if !OfflineThreshold::<T>::get().map_or(false, |threshold| sessions >= threshold) {
+			return
+		}
+		// Suspended validators don't count towards the minimum, so can always be removed
+		if !SuspendedValidators::<T>::contains_key(who) &&
+			Self::num_active_validators() <= T::MinActiveValidators::get()
+		{
+			log::debug!(
+				target: LOG_TARGET,
+				"Not removing offline validator {:?}, too few active validators", who
+			);
+			return
+		}
+		if Self::do_remove_validator(who) {
+			Self::deposit_event(Event::ValidatorRemoved(who.clone()));
+		}
+	}
+
 	fn check_min_set_keys_block(
 		validator: &Validator<BlockNumberFor<T>>,
 	) -> Result<(), TransactionValidityError> {
@@ -330,19 +495,21 @@ where
 
 		for (offender, slash_fraction) in offenders.iter().zip(slash_fractions) {
 			// Determine actions to take with this validator
-			let remove = !slash_fraction.is_zero();
+			let suspend = !slash_fraction.is_zero();
 			let disable = match disable_strategy {
 				DisableStrategy::Never => false,
 				DisableStrategy::WhenSlashed => !slash_fraction.is_zero(),
 				DisableStrategy::Always => true,
 			};
 
-			if remove {
-				// Note that the validator might already have been removed (explicitly, for another
-				// offence, or even by an earlier report of this offence)
-				weight.saturating_accrue(db_weight.reads(1));
-				if Self::do_remove_validator(&offender.offender.0) {
-					weight.saturating_accrue(db_weight.reads_writes(1, 2));
+			if suspend {
+				// Note that the validator might already have been removed or suspended (for
+				// another offence, or even by an earlier report of this offence)
+				weight.saturating_accrue(db_weight.reads(4));
+				if Self::do_suspend_validator(&offender.offender.0) {
+					// SuspendedValidators and NumSuspendedValidators
+					weight.saturating_accrue(db_weight.writes(2));
+					Self::deposit_event(Event::ValidatorSuspended(offender.offender.0.clone()));
 				}
 			}
 
@@ -354,8 +521,8 @@ where
 					weight.saturating_accrue(db_weight.writes(1));
 				}
 
-				// Also disable in the next session, as removal won't take effect until the session
-				// after next
+				// Also disable in the next session, as suspension won't take effect until the
+				// session after next
 				weight.saturating_accrue(db_weight.writes(1));
 				NextDisabledValidators::<T>::insert(&offender.offender.0, ());
 			}
@@ -364,3 +531,33 @@ where
 		weight
 	}
 }
+
+/// [`ReportOffence`] implementation for unresponsiveness offences, such as those reported by
+/// `pallet_im_online`. Offences are passed on to `R`, and the offenders of new offences are noted
+/// as offline in the offence's session.
+pub struct ReportOffline<T, R>(PhantomData<(T, R)>);
+
+impl<T, R, O> ReportOffence<T::AccountId, pallet_session::historical::IdentificationTuple<T>, O>
+	for ReportOffline<T, R>
+where
+	T: Config + pallet_session::historical::Config,
+	R: ReportOffence<T::AccountId, pallet_session::historical::IdentificationTuple<T>, O>,
+	O: Offence<pallet_session::historical::IdentificationTuple<T>>,
+{
+	fn report_offence(reporters: Vec<T::AccountId>, offence: O) -> Result<(), OffenceError> {
+		let session = offence.session_index();
+		let offenders = offence.offenders();
+		R::report_offence(reporters, offence)?;
+		for (who, _) in offenders {
+			Pallet::<T>::note_offline(&who, session);
+		}
+		Ok(())
+	}
+
+	fn is_known_offence(
+		offenders: &[pallet_session::historical::IdentificationTuple<T>],
+		time_slot: &O::TimeSlot,
+	) -> bool {
+		R::is_known_offence(offenders, time_slot)
+	}
+}
@@ -155,6 +155,7 @@ impl pallet_validator_set::Config for Test {
 	type AddRemoveOrigin = EnsureRoot<Self::AccountId>;
 	type MaxAuthorities = ConstU32<6>;
 	type SetKeysCooldownBlocks = SetKeysCooldownBlocks;
+	type MinActiveValidators = ConstU32<2>;
 }
 
 impl pallet_session::Config for Test {
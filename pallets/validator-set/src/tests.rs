@@ -18,19 +18,65 @@
 
 #![cfg(test)]
 
-use super::mock::{
-	new_test_ext, next_block, next_session, AccountId, RuntimeOrigin, Session, System, Test,
-	ValidatorSet,
+use super::{
+	mock::{
+		new_test_ext, next_block, next_session, AccountId, RuntimeEvent, RuntimeOrigin, Session,
+		System, Test, ValidatorSet,
+	},
+	ReportOffline,
 };
 use frame_support::{
 	assert_noop, assert_ok,
 	traits::{DisabledValidators, ValidatorRegistration},
 };
 use sp_runtime::{traits::Zero, transaction_validity::InvalidTransaction, DispatchError, Perbill};
-use sp_staking::offence::{DisableStrategy, OffenceDetails, OnOffenceHandler};
+use sp_staking::{
+	offence::{DisableStrategy, Kind, Offence, OffenceDetails, OnOffenceHandler, ReportOffence},
+	SessionIndex,
+};
 use std::collections::HashSet;
 
 type Error = super::Error<Test>;
+type Event = super::Event<Test>;
+
+/// Unresponsiveness offence, as reported by `pallet_im_online`.
+struct OfflineOffence {
+	session_index: SessionIndex,
+	offenders: Vec<(AccountId, AccountId)>,
+}
+
+impl Offence<(AccountId, AccountId)> for OfflineOffence {
+	const ID: Kind = *b"test:offline----";
+	type TimeSlot = SessionIndex;
+
+	fn offenders(&self) -> Vec<(AccountId, AccountId)> {
+		self.offenders.clone()
+	}
+
+	fn session_index(&self) -> SessionIndex {
+		self.session_index
+	}
+
+	fn validator_set_count(&self) -> u32 {
+		3
+	}
+
+	fn time_slot(&self) -> SessionIndex {
+		self.session_index
+	}
+
+	fn slash_fraction(&self, _offenders_count: u32) -> Perbill {
+		Perbill::zero()
+	}
+}
+
+fn report_offline(session_index: SessionIndex, offenders: &[AccountId]) {
+	let offenders = offenders.iter().map(|who| (*who, *who)).collect();
+	assert_ok!(ReportOffline::<Test, ()>::report_offence(
+		vec![],
+		OfflineOffence { session_index, offenders }
+	));
+}
 
 fn validators() -> HashSet<AccountId> {
 	ValidatorSet::validators().into_iter().collect()
@@ -142,8 +188,9 @@ fn remove_purges_keys_and_decs_providers() {
 }
 
 #[test]
-fn offender_disabled_and_removed() {
+fn offender_disabled_and_suspended() {
 	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
 		assert_eq!(validators(), HashSet::from([1, 2, 3]));
 		ValidatorSet::on_offence(
 			&[OffenceDetails { offender: (3, 3), reporters: vec![] }],
@@ -151,10 +198,14 @@ fn offender_disabled_and_removed() {
 			0,
 			DisableStrategy::WhenSlashed,
 		);
+		System::assert_last_event(RuntimeEvent::ValidatorSet(Event::ValidatorSuspended(3)));
 		assert_eq!(validators(), HashSet::from([1, 2]));
+		assert_eq!(ValidatorSet::suspended_validators(), vec![3]);
+		// Suspended validators keep their registration pending review
+		assert!(Session::is_registered(&3));
 
 		// The offender should be disabled for the rest of this session and the next session. The
-		// removal should take effect by the session after next.
+		// suspension should take effect by the session after next.
 		assert_eq!(active_validators(), HashSet::from([1, 2, 3]));
 		assert!(Session::is_disabled(
 			Session::validators().iter().position(|who| *who == 3).unwrap() as u32
@@ -169,6 +220,41 @@ fn offender_disabled_and_removed() {
 	});
 }
 
+#[test]
+fn suspended_validator_reinstated() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		ValidatorSet::on_offence(
+			&[OffenceDetails { offender: (3, 3), reporters: vec![] }],
+			&[Perbill::from_rational(1u32, 2u32)],
+			0,
+			DisableStrategy::WhenSlashed,
+		);
+		next_session();
+		next_session();
+		assert_eq!(active_validators(), HashSet::from([1, 2]));
+
+		assert_noop!(
+			ValidatorSet::reinstate_validator(RuntimeOrigin::signed(1), 3),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			ValidatorSet::reinstate_validator(RuntimeOrigin::root(), 2),
+			Error::NotSuspended
+		);
+		// A suspended validator can't be added again, only reinstated
+		assert_noop!(ValidatorSet::add_validator(RuntimeOrigin::root(), 3), Error::Duplicate);
+		assert_ok!(ValidatorSet::reinstate_validator(RuntimeOrigin::root(), 3));
+		System::assert_last_event(RuntimeEvent::ValidatorSet(Event::ValidatorReinstated(3)));
+		assert_eq!(validators(), HashSet::from([1, 2, 3]));
+		assert!(ValidatorSet::suspended_validators().is_empty());
+
+		next_session();
+		next_session();
+		assert_eq!(active_validators(), HashSet::from([1, 2, 3]));
+	});
+}
+
 #[test]
 fn non_validator_cant_set_keys() {
 	new_test_ext().execute_with(|| {
@@ -188,3 +274,132 @@ fn set_keys_has_cooldown() {
 		assert_ok!(ValidatorSet::pre_dispatch_set_keys(&3));
 	});
 }
+
+#[test]
+fn persistently_offline_validator_removed() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			ValidatorSet::set_offline_threshold(RuntimeOrigin::signed(1), Some(3)),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(ValidatorSet::set_offline_threshold(RuntimeOrigin::root(), Some(3)));
+		System::assert_last_event(RuntimeEvent::ValidatorSet(Event::OfflineThresholdSet {
+			threshold: Some(3),
+		}));
+
+		report_offline(0, &[2, 3, 4]);
+		report_offline(1, &[3]);
+		assert_eq!(ValidatorSet::offline_sessions(&2), 1);
+		assert_eq!(ValidatorSet::offline_sessions(&3), 2);
+		// Not a validator
+		assert_eq!(ValidatorSet::offline_sessions(&4), 0);
+
+		// Only consecutive sessions count
+		report_offline(3, &[2, 3]);
+		assert_eq!(ValidatorSet::offline_sessions(&2), 1);
+		assert_eq!(ValidatorSet::offline_sessions(&3), 1);
+		report_offline(4, &[3]);
+		report_offline(5, &[3]);
+		System::assert_has_event(RuntimeEvent::ValidatorSet(Event::ValidatorOffline {
+			who: 3,
+			sessions: 3,
+		}));
+		System::assert_last_event(RuntimeEvent::ValidatorSet(Event::ValidatorRemoved(3)));
+		assert_eq!(validators(), HashSet::from([1, 2]));
+		assert_eq!(ValidatorSet::offline_sessions(&3), 0);
+
+		// Without a threshold, offline validators are only flagged
+		assert_ok!(ValidatorSet::set_offline_threshold(RuntimeOrigin::root(), None));
+		report_offline(6, &[2]);
+		report_offline(7, &[2]);
+		report_offline(8, &[2]);
+		assert_eq!(ValidatorSet::offline_sessions(&2), 3);
+		assert_eq!(validators(), HashSet::from([1, 2]));
+	});
+}
+
+#[test]
+fn offline_validator_not_removed_below_minimum() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(ValidatorSet::set_offline_threshold(RuntimeOrigin::root(), Some(1)));
+
+		report_offline(0, &[3]);
+		System::assert_last_event(RuntimeEvent::ValidatorSet(Event::ValidatorRemoved(3)));
+		assert_eq!(validators(), HashSet::from([1, 2]));
+
+		// Removing another validator would leave fewer than MinActiveValidators
+		report_offline(1, &[2]);
+		System::assert_last_event(RuntimeEvent::ValidatorSet(Event::ValidatorOffline {
+			who: 2,
+			sessions: 1,
+		}));
+		assert_eq!(validators(), HashSet::from([1, 2]));
+		assert_eq!(ValidatorSet::offline_sessions(&2), 1);
+
+		// Once another validator is added, it can be removed
+		assert_ok!(ValidatorSet::add_validator(RuntimeOrigin::root(), 4));
+		report_offline(2, &[2]);
+		System::assert_last_event(RuntimeEvent::ValidatorSet(Event::ValidatorRemoved(2)));
+		assert_eq!(validators(), HashSet::from([1, 4]));
+	});
+}
+
+#[test]
+fn last_active_validator_not_suspended() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		ValidatorSet::on_offence(
+			&[
+				OffenceDetails { offender: (1, 1), reporters: vec![] },
+				OffenceDetails { offender: (2, 2), reporters: vec![] },
+				OffenceDetails { offender: (3, 3), reporters: vec![] },
+			],
+			&[Perbill::from_rational(1u32, 2u32); 3],
+			0,
+			DisableStrategy::Never,
+		);
+		assert_eq!(validators(), HashSet::from([3]));
+		assert_eq!(ValidatorSet::suspended_validators().len(), 2);
+
+		// Once another validator is reinstated, the last one can be suspended
+		assert_ok!(ValidatorSet::reinstate_validator(RuntimeOrigin::root(), 1));
+		ValidatorSet::on_offence(
+			&[OffenceDetails { offender: (3, 3), reporters: vec![] }],
+			&[Perbill::from_rational(1u32, 2u32)],
+			0,
+			DisableStrategy::Never,
+		);
+		System::assert_last_event(RuntimeEvent::ValidatorSet(Event::ValidatorSuspended(3)));
+		assert_eq!(validators(), HashSet::from([1]));
+
+		next_session();
+		next_session();
+		assert_eq!(active_validators(), HashSet::from([1]));
+	});
+}
+
+#[test]
+fn removed_suspended_validator_no_longer_counted() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let suspend = |who| {
+			ValidatorSet::on_offence(
+				&[OffenceDetails { offender: (who, who), reporters: vec![] }],
+				&[Perbill::from_rational(1u32, 2u32)],
+				0,
+				DisableStrategy::Never,
+			);
+		};
+		suspend(3);
+		assert_ok!(ValidatorSet::remove_validator(RuntimeOrigin::root(), 3));
+		assert!(ValidatorSet::suspended_validators().is_empty());
+
+		// Validators 1 and 2 remain active, so one of them can still be suspended
+		suspend(2);
+		System::assert_last_event(RuntimeEvent::ValidatorSet(Event::ValidatorSuspended(2)));
+		suspend(1);
+		assert_eq!(validators(), HashSet::from([1]));
+	});
+}
@@ -20,6 +20,9 @@
 //! DATE: 2023-05-29, STEPS: `50`, REPEAT: `20`, LOW RANGE: `[]`, HIGH RANGE: `[]`
 //! WORST CASE MAP SIZE: `1000000`
 //! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("local"), DB CACHE: 1024
+//!
+//! Weights marked as placeholders have not been benchmarked yet, and must be regenerated with
+//! the command below before being relied upon.
 
 // Executed Command:
 // ./target/release/node-template
@@ -51,6 +54,8 @@ use core::marker::PhantomData;
 pub trait WeightInfo {
 	fn add_validator() -> Weight;
 	fn remove_validator() -> Weight;
+	fn set_offline_threshold() -> Weight;
+	fn reinstate_validator() -> Weight;
 }
 
 /// Weights for validator_set using the Substrate node and recommended hardware.
@@ -78,6 +83,17 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Placeholder, not yet benchmarked.
+	fn set_offline_threshold() -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Placeholder, not yet benchmarked.
+	fn reinstate_validator() -> Weight {
+		Weight::from_parts(20_000_000, 1602)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -104,5 +120,16 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Placeholder, not yet benchmarked.
+	fn set_offline_threshold() -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Placeholder, not yet benchmarked.
+	fn reinstate_validator() -> Weight {
+		Weight::from_parts(20_000_000, 1602)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
 }
 
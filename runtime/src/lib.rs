@@ -178,14 +178,17 @@ parameter_types! {
 	// This currently _must_ be set to DEFAULT_STORAGE_PERIOD
 	pub const StoragePeriod: BlockNumber = sp_transaction_storage_proof::DEFAULT_STORAGE_PERIOD;
 	pub const AuthorizationPeriod: BlockNumber = 7 * DAYS;
-	pub const StoreRenewPriority: TransactionPriority = RemoveExpiredAuthorizationPriority::get() - 1;
+	pub const StoreRenewPriority: TransactionPriority = OperationalStorePriority::get() - 1;
 	pub const StoreRenewLongevity: TransactionLongevity = DAYS as TransactionLongevity;
+	pub const OperationalStorePriority: TransactionPriority =
+		RemoveExpiredAuthorizationPriority::get() - 1;
 	pub const RemoveExpiredAuthorizationPriority: TransactionPriority = SetKeysPriority::get() - 1;
 	pub const RemoveExpiredAuthorizationLongevity: TransactionLongevity = DAYS as TransactionLongevity;
 
 	pub const SudoPriority: TransactionPriority = ImOnlineUnsignedPriority::get() - 1;
 
 	pub const SetKeysCooldownBlocks: BlockNumber = 5 * MINUTES;
+	pub const MinActiveValidators: u32 = 2;
 	pub const SetKeysPriority: TransactionPriority = SudoPriority::get() - 1;
 	pub const SetKeysLongevity: TransactionLongevity = HOURS as TransactionLongevity;
 }
@@ -248,6 +251,7 @@ impl pallet_validator_set::Config for Runtime {
 	type AddRemoveOrigin = EnsureRoot<AccountId>;
 	type MaxAuthorities = MaxAuthorities;
 	type SetKeysCooldownBlocks = SetKeysCooldownBlocks;
+	type MinActiveValidators = MinActiveValidators;
 }
 
 impl pallet_session::Config for Runtime {
@@ -318,7 +322,7 @@ impl pallet_im_online::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type ValidatorSet = Historical;
 	type NextSessionRotation = Babe;
-	type ReportUnresponsiveness = Offences;
+	type ReportUnresponsiveness = pallet_validator_set::ReportOffline<Runtime, Offences>;
 	type UnsignedPriority = ImOnlineUnsignedPriority;
 	type WeightInfo = pallet_im_online::weights::SubstrateWeight<Runtime>;
 }
@@ -347,8 +351,18 @@ impl pallet_transaction_storage::Config for Runtime {
 	type Authorizer = EnsureRoot<Self::AccountId>;
 	type StoreRenewPriority = StoreRenewPriority;
 	type StoreRenewLongevity = StoreRenewLongevity;
+	type OperationalStorePriority = OperationalStorePriority;
 	type RemoveExpiredAuthorizationPriority = RemoveExpiredAuthorizationPriority;
 	type RemoveExpiredAuthorizationLongevity = RemoveExpiredAuthorizationLongevity;
+	type MaxUploadChunks = ConstU32<1024>;
+	// Leave room in the block for bridge and consensus transactions
+	type MaxBlockBytes = ConstU32<{ 8 * 1024 * 1024 }>;
+	type UploadTimeout = ConstU32<HOURS>;
+	type OnDataExpired = ();
+	type PayloadValidator = ();
+	type UsagePeriod = ConstU32<DAYS>;
+	type RevealPeriod = ConstU32<{ 30 * DAYS }>;
+	type Randomness = pallet_babe::ParentBlockRandomness<Runtime>;
 }
 
 impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime
@@ -667,6 +681,69 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_transaction_storage::runtime_api::TransactionStorageApi<Block, AccountId, BlockNumber>
+		for Runtime
+	{
+		fn authorization_status(
+			who: AccountId,
+		) -> Option<pallet_transaction_storage::AuthorizationStatus<BlockNumber>> {
+			TransactionStorage::account_authorization_status(who)
+		}
+
+		fn lookup(hash: [u8; 32]) -> Option<(BlockNumber, u32)> {
+			TransactionStorage::content_location(hash)
+		}
+
+		fn block_bytes_remaining() -> u64 {
+			TransactionStorage::block_bytes_remaining()
+		}
+
+		fn data_status(hash: [u8; 32]) -> pallet_transaction_storage::DataStatus<BlockNumber> {
+			TransactionStorage::data_status(hash)
+		}
+
+		fn transactions_key(block: BlockNumber) -> Vec<u8> {
+			TransactionStorage::transactions_key(block)
+		}
+
+		fn resolve_name(name: Vec<u8>) -> Option<[u8; 32]> {
+			TransactionStorage::resolve_name(&name)
+		}
+
+		fn storage_usage() -> pallet_transaction_storage::StorageUsage<BlockNumber> {
+			TransactionStorage::storage_usage()
+		}
+
+		fn upload_receipt(
+			root: [u8; 32],
+		) -> Option<pallet_transaction_storage::UploadReceipt<BlockNumber>> {
+			TransactionStorage::upload_receipt(root)
+		}
+
+		fn upload_chunk_proof(
+			root: [u8; 32],
+			chunk: u32,
+		) -> Option<pallet_transaction_storage::ChunkProof> {
+			TransactionStorage::upload_chunk_proof(root, chunk)
+		}
+
+		fn commit_block(hash: [u8; 32]) -> Option<BlockNumber> {
+			TransactionStorage::commit_block(hash)
+		}
+
+		fn storage_proof_randomness() -> Option<[u8; 32]> {
+			TransactionStorage::proof_randomness().map(Into::into)
+		}
+
+		fn expiring_within(
+			blocks: BlockNumber,
+			skip: u32,
+			limit: u32,
+		) -> Vec<([u8; 32], Option<pallet_transaction_storage::Namespace>, BlockNumber)> {
+			TransactionStorage::expiring_within(blocks, skip, limit)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (